@@ -11,6 +11,12 @@ pub const VERSION_STRING: &str = env!("CARGO_PKG_VERSION");
 pub struct ZKSyncCLI {
     #[command(subcommand)]
     command: ZKSyncCommand,
+    #[arg(
+        long,
+        global = true,
+        help = "Alias of the config wallet to use instead of the default one"
+    )]
+    wallet: Option<String>,
 }
 
 #[derive(Subcommand)]
@@ -56,16 +62,17 @@ enum ZKSyncCommand {
 }
 
 pub async fn start() -> eyre::Result<()> {
-    let ZKSyncCLI { command } = ZKSyncCLI::parse();
+    let ZKSyncCLI { command, wallet } = ZKSyncCLI::parse();
     if let ZKSyncCommand::Config(cmd) = command {
         return cmd.run().await;
     }
-    let cfg = load_selected_config().await?;
+    let mut cfg = load_selected_config().await?;
+    cfg.selected_wallet = wallet;
     match command {
         ZKSyncCommand::Wallet(cmd) => cmd.run(cfg).await?,
         ZKSyncCommand::Chain(cmd) => cmd.run(cfg).await?,
         ZKSyncCommand::Prover(cmd) => cmd.run(cfg).await?,
-        ZKSyncCommand::Contract(cmd) => cmd.run(cfg)?,
+        ZKSyncCommand::Contract(cmd) => cmd.run(cfg).await?,
         ZKSyncCommand::Contracts(cmd) => cmd.run(cfg).await?,
         ZKSyncCommand::Autocomplete(cmd) => cmd.run()?,
         ZKSyncCommand::Utils(cmd) => cmd.run(cfg)?,
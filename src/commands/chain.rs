@@ -3,12 +3,13 @@ use crate::{
     utils::{
         balance::{display_l1_balance, display_l2_balance},
         chain::{display_batches_details, display_batches_proof_time_from_l1_batch_details},
+        signed_tx::{Layer, SignedTransaction},
         try_l1_provider_from_config, try_l2_provider_from_config,
     },
 };
 use clap::Subcommand;
 use eyre::ContextCompat;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 use zksync_ethers_rs::{
     abi::Hash,
     core::utils::format_ether,
@@ -147,6 +148,13 @@ pub(crate) enum Command {
         #[clap(long, short = 'e', required = false)]
         explorer_url: bool,
     },
+    #[clap(about = "Broadcasts a transaction signed with --sign-only.")]
+    SendRaw {
+        #[clap(help = "Signed transaction file written by --sign-only")]
+        file: PathBuf,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+    },
 }
 
 impl Command {
@@ -408,6 +416,40 @@ impl Command {
                     println!("Deposit finalization hash: {deposit_finalization_hash:#?}");
                 }
             }
+            Command::SendRaw { file, explorer_url } => {
+                let signed_tx = SignedTransaction::read(&file)?;
+                let (provider, layer_explorer_url) = match signed_tx.layer {
+                    Layer::L1 => (&l1_provider, l1_explorer_url),
+                    Layer::L2 => (&l2_provider, l2_explorer_url),
+                };
+                let chain_id = provider.get_chainid().await?;
+                if chain_id != signed_tx.chain_id.into() {
+                    eyre::bail!(
+                        "The transaction was signed for chain {} but the {} RPC is chain {chain_id}",
+                        signed_tx.chain_id,
+                        signed_tx.layer
+                    );
+                }
+                let pending_transaction = provider
+                    .send_raw_transaction(signed_tx.raw_transaction)
+                    .await?;
+                let tx_hash = pending_transaction.tx_hash();
+                if explorer_url {
+                    println!("Transaction sent: {layer_explorer_url}/tx/{tx_hash:?}");
+                } else {
+                    println!("Transaction sent: {tx_hash:?}");
+                }
+                let receipt = pending_transaction
+                    .await?
+                    .context("No transaction receipt")?;
+                if receipt.status.is_some_and(|status| status.is_zero()) {
+                    eyre::bail!("Transaction {tx_hash:?} reverted");
+                }
+                println!(
+                    "Transaction included in block {:?}",
+                    receipt.block_number.unwrap_or_default()
+                );
+            }
         };
         Ok(())
     }
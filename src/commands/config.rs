@@ -53,6 +53,37 @@ pub struct EditConfigOpts {
     pub l1_explorer_url: Option<String>,
     #[arg(long, requires = "config_name", required = false)]
     pub private_key: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        conflicts_with = "private_key",
+        help = "Name of the keystore in ~/.config/zks-cli/keystores to use instead of a private key"
+    )]
+    pub keystore: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        conflicts_with_all = ["private_key", "keystore"],
+        help = "BIP-39 mnemonic to derive the wallet from instead of a private key"
+    )]
+    pub mnemonic: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Index in the m/44'/60'/0'/0/{index} derivation path of the mnemonic"
+    )]
+    pub derivation_index: Option<u32>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        conflicts_with_all = ["private_key", "keystore", "mnemonic"],
+        help = "JSON-RPC endpoint of a remote signer holding the key of the wallet address"
+    )]
+    pub remote_signer_url: Option<String>,
     #[arg(long, requires = "config_name", required = false)]
     pub address: Option<Address>,
     #[arg(long, requires = "config_name", required = false)]
@@ -63,6 +94,49 @@ pub struct EditConfigOpts {
     pub bridgehub_admin: Option<String>,
     #[arg(long, requires = "config_name", required = false)]
     pub bridgehub_owner: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Safe owning the Governance, whose transactions are then sent through it"
+    )]
+    pub governance_owner_safe: Option<Address>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        conflicts_with = "governance_security_council_wallet",
+        help = "Private key of the security council, to execute operations instantly"
+    )]
+    pub governance_security_council: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Alias of the configured wallet being the security council"
+    )]
+    pub governance_security_council_wallet: Option<String>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Safe being the Bridgehub admin"
+    )]
+    pub bridgehub_admin_safe: Option<Address>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Safe owning the Bridgehub"
+    )]
+    pub bridgehub_owner_safe: Option<Address>,
+    #[arg(
+        long,
+        requires = "config_name",
+        required = false,
+        help = "Bridgehub address, fetched from the L2 node if not set"
+    )]
+    pub bridgehub_address: Option<Address>,
     #[arg(long, requires = "config_name", required = false)]
     pub server_db_url: Option<Database>,
     #[arg(long, requires = "config_name", required = false)]
@@ -76,11 +150,21 @@ impl EditConfigOpts {
             && self.l2_explorer_url.is_none()
             && self.l2_rpc_url.is_none()
             && self.private_key.is_none()
+            && self.keystore.is_none()
+            && self.mnemonic.is_none()
+            && self.derivation_index.is_none()
+            && self.remote_signer_url.is_none()
             && self.address.is_none()
             && self.governance.is_none()
             && self.governance_owner.is_none()
             && self.bridgehub_admin.is_none()
             && self.bridgehub_owner.is_none()
+            && self.governance_owner_safe.is_none()
+            && self.governance_security_council.is_none()
+            && self.governance_security_council_wallet.is_none()
+            && self.bridgehub_admin_safe.is_none()
+            && self.bridgehub_owner_safe.is_none()
+            && self.bridgehub_address.is_none()
             && self.server_db_url.is_none()
             && self.prover_db_url.is_none()
     }
@@ -1,5 +1,19 @@
 use crate::config::ZKSyncConfig;
+use crate::utils::{
+    balance::get_base_token_decimals_symbol,
+    contract::{encode_call, get_fn_selector, parse_signature},
+    paymaster::{send_l2_transaction_with_paymaster, PaymasterOpts},
+    signed_tx::sign_l2_transaction,
+    tx::{estimate_l2_fee, TxOpts},
+};
 use clap::Subcommand;
+use eyre::ContextCompat;
+use zksync_ethers_rs::{
+    providers::Middleware,
+    types::{Address, Eip1559TransactionRequest, U256},
+    zk_wallet::ZKWallet,
+    ZKMiddleware,
+};
 
 #[derive(Subcommand)]
 pub(crate) enum Command {
@@ -17,13 +31,18 @@ pub(crate) enum Command {
     #[clap(about = "Call non-view functions on a contract.")]
     Send {
         contract_address: String,
+        #[clap(help = "Function signature, e.g. set(uint256)")]
         function_name: String,
         args: Vec<String>,
+        #[command(flatten)]
+        paymaster: PaymasterOpts,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
 }
 
 impl Command {
-    pub fn run(self, _cfg: ZKSyncConfig) -> eyre::Result<()> {
+    pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
         match self {
             Command::Call {
                 contract_address: _,
@@ -35,10 +54,83 @@ impl Command {
                 constructor_args: _,
             } => todo!("Deploy"),
             Command::Send {
-                contract_address: _,
-                function_name: _,
-                args: _,
-            } => todo!(),
+                contract_address,
+                function_name,
+                args,
+                paymaster,
+                tx_opts,
+            } => {
+                let contract_address: Address = contract_address.parse()?;
+                let calldata = encode_call(
+                    Some(get_fn_selector(&function_name)),
+                    None,
+                    (!args.is_empty()).then_some(args),
+                    parse_signature(&function_name)?,
+                )?;
+                let zk_wallet = ZKWallet::try_from(&cfg)?;
+                let paymaster_params = paymaster.paymaster_params()?;
+                if paymaster_params.is_some() || tx_opts.sign_opts.sign_only.is_some() {
+                    zk_wallet.l2_signer().signer().ensure_typed_data_support()?;
+                }
+                if tx_opts.estimate {
+                    let l2_provider = zk_wallet.l2_provider();
+                    let (base_token_decimals, base_token_symbol) = get_base_token_decimals_symbol(
+                        l2_provider.get_base_token_l1_address().await?,
+                        zk_wallet.l1_provider(),
+                    )
+                    .await?;
+                    estimate_l2_fee(
+                        l2_provider,
+                        zk_wallet.l2_address(),
+                        contract_address,
+                        U256::zero(),
+                        &calldata,
+                        paymaster_params.as_ref(),
+                    )
+                    .await?
+                    .display(base_token_decimals, &base_token_symbol)?;
+                }
+                if !tx_opts.confirm("Send the transaction")? {
+                    return Ok(());
+                }
+                if let Some(signed_tx_path) = &tx_opts.sign_opts.sign_only {
+                    sign_l2_transaction(
+                        &zk_wallet,
+                        contract_address,
+                        U256::zero(),
+                        calldata,
+                        paymaster_params,
+                        &tx_opts.sign_opts,
+                    )
+                    .await?
+                    .write(signed_tx_path)?;
+                    return Ok(());
+                }
+                let tx_hash = if let Some(paymaster_params) = paymaster_params {
+                    send_l2_transaction_with_paymaster(
+                        &zk_wallet,
+                        contract_address,
+                        U256::zero(),
+                        calldata,
+                        paymaster_params,
+                    )
+                    .await?
+                } else {
+                    let tx = Eip1559TransactionRequest::new()
+                        .from(zk_wallet.l2_address())
+                        .to(contract_address)
+                        .data(calldata);
+                    zk_wallet
+                        .l2_signer()
+                        .send_transaction(tx, None)
+                        .await?
+                        .await?
+                        .context("No transaction receipt")?
+                        .transaction_hash
+                };
+                println!("Transaction hash: {tx_hash:?}");
+            }
         };
+        Ok(())
     }
 }
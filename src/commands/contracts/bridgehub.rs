@@ -1,17 +1,34 @@
-use std::sync::Arc;
-
 use crate::{
     config::ZKSyncConfig,
-    utils::{contracts::try_bridgehub_from_config, try_l1_signer_from_config},
+    utils::{
+        balance::get_erc20_decimals_symbol,
+        contract::get_fn_selector,
+        contracts::{bridgehub_address, pending_role_wallet, try_bridgehub_from_config},
+        logs::get_logs_in_range,
+        signer::ConfigSigner,
+        try_l1_provider_from_config, try_l1_signer_from_config,
+        tx::{send_l1_tx, TxOpts},
+        wallet::ETH_TOKEN_ADDRESS,
+    },
 };
 use clap::Subcommand;
 use eyre::ContextCompat;
+use std::{path::PathBuf, str::FromStr, sync::Arc};
 use zksync_ethers_rs::{
     contracts::bridgehub::Bridgehub,
-    signers::LocalWallet,
-    types::{Address, U256},
+    core::utils::keccak256,
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, Filter,
+        TransactionReceipt, H256, U256,
+    },
+    ZKMiddleware,
 };
 
+const NEW_CHAIN_EVENT: &str = "NewChain(uint256,address,address)";
+
 #[derive(Subcommand)]
 pub(crate) enum Command {
     #[clap(
@@ -32,17 +49,146 @@ pub(crate) enum Command {
     },
     #[clap(about = "Get the bridge contract admin address.")]
     Admin,
+    #[clap(
+        about = "List the chains registered in the Bridgehub, with their STM, base token, hyperchain, admin and protocol version."
+    )]
+    Chains {
+        #[clap(
+            long,
+            required = true,
+            help = "Block to scan the events from, e.g. the Bridgehub deployment block"
+        )]
+        from_block: u64,
+        #[clap(long, required = false, help = "Defaults to the latest block")]
+        to_block: Option<u64>,
+        #[clap(long, required = false)]
+        json: bool,
+    },
     #[clap(
         about = "Set a new admin of the Bridgehub. Only the Bridgehub owner or the current admin can do this.",
         visible_alias = "spa"
     )]
-    SetPendingAdmin { new_pending_admin: Address },
+    SetPendingAdmin {
+        new_pending_admin: Address,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
     #[clap(
         about = "Accept the admin of the Bridgehub. Only the pending admin can do this.",
         visible_alias = "aa"
     )]
     AcceptAdmin {
-        pending_admin_private_key: LocalWallet,
+        #[clap(help = "Defaults to the wallet selected with --wallet or the configured one")]
+        pending_admin_private_key: Option<LocalWallet>,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Register a token to be used as base token. Only the Bridgehub owner can do this.",
+        visible_alias = "at"
+    )]
+    AddToken {
+        token: Address,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Register a StateTransitionManager. Only the Bridgehub owner can do this.",
+        visible_alias = "astm"
+    )]
+    AddStateTransitionManager {
+        state_transition_manager: Address,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Unregister a StateTransitionManager. Only the Bridgehub owner can do this.",
+        visible_alias = "rstm"
+    )]
+    RemoveStateTransitionManager {
+        state_transition_manager: Address,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Set the shared bridge of the Bridgehub. Only the Bridgehub owner can do this.",
+        visible_alias = "ssb"
+    )]
+    SetSharedBridge {
+        shared_bridge: Address,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(about = "Pause the Bridgehub. Only the Bridgehub owner can do this.")]
+    Pause {
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(about = "Unpause the Bridgehub. Only the Bridgehub owner can do this.")]
+    Unpause {
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Start the transfer of the Bridgehub ownership. Only the Bridgehub owner can do this.",
+        visible_alias = "to"
+    )]
+    TransferOwnership {
+        new_owner: Address,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Accept the ownership of the Bridgehub. Only the pending owner can do this.",
+        visible_alias = "ao"
+    )]
+    AcceptOwnership {
+        #[clap(help = "Defaults to the wallet selected with --wallet or the configured one")]
+        pending_owner_private_key: Option<LocalWallet>,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        about = "Register a new hyperchain. Only the Bridgehub owner or admin can do this.",
+        visible_alias = "cnc"
+    )]
+    CreateNewChain {
+        #[clap(long, value_parser = U256::from_dec_str)]
+        chain_id: U256,
+        #[clap(long = "stm", help = "StateTransitionManager of the chain")]
+        state_transition_manager: Address,
+        #[clap(long, help = "L1 address of the base token, 0x...01 for ETH")]
+        base_token: Address,
+        #[clap(long, value_parser = U256::from_dec_str)]
+        salt: U256,
+        #[clap(long, help = "Admin of the new hyperchain")]
+        admin: Address,
+        #[clap(
+            long,
+            value_name = "FILE",
+            help = "File with the hex encoded diamond cut initialization data of the hyperchain"
+        )]
+        init_data: PathBuf,
+        #[clap(long, short = 'e', required = false)]
+        explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
     #[clap(
         about = "Get the Hyperchain contract address of a chain.",
@@ -56,6 +202,14 @@ pub(crate) enum Command {
 
 impl Command {
     pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
+        if let Command::Chains {
+            from_block,
+            to_block,
+            json,
+        } = self
+        {
+            return list_chains(from_block, to_block, json, &cfg).await;
+        }
         let bridgehub = try_bridgehub_from_config(&cfg).await?;
         match self {
             Command::StateTransitionManager { chain_id } => {
@@ -75,29 +229,45 @@ impl Command {
                     println!("Bridgehub admin: {bridgehub_admin:?}");
                 }
             }
-            Command::SetPendingAdmin { new_pending_admin } => {
-                // TODO: Do not repeat the same code for calling the set_pending_admin method.
-                let transaction_receipt = if let Some(ref admin_private_key) =
-                    cfg.bridgehub.admin_private_key
-                {
-                    let current_admin =
-                        try_l1_signer_from_config(admin_private_key.parse()?, &cfg).await?;
-                    // We need to instantiate a Bridgehub with the pending admin as the signer
-                    // to be able to call accept_admin
-                    let bridgehub = Bridgehub::new(bridgehub.address(), Arc::new(current_admin));
-                    bridgehub
-                        .set_pending_admin(new_pending_admin)
-                        .send()
-                        .await?
-                        .await?
-                        .context("No transaction receipt for bridgehub admin acceptance")?
-                } else {
-                    bridgehub
-                        .set_pending_admin(new_pending_admin)
-                        .send()
+            Command::SetPendingAdmin {
+                new_pending_admin,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .set_pending_admin(new_pending_admin)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                let transaction_receipt =
+                    if let Some(ref admin_private_key) = cfg.bridgehub.admin_private_key {
+                        let current_admin = try_l1_signer_from_config(
+                            ConfigSigner::Local(admin_private_key.parse()?),
+                            &cfg,
+                        )
+                        .await?;
+                        send_l1_tx(
+                            &current_admin,
+                            bridgehub.address(),
+                            calldata,
+                            cfg.bridgehub.admin_safe,
+                            "Set the Bridgehub pending admin",
+                            &tx_opts,
+                            &cfg,
+                        )
                         .await?
+                    } else {
+                        send_l1_tx(
+                            &bridgehub.client(),
+                            bridgehub.address(),
+                            calldata,
+                            cfg.bridgehub.owner_safe,
+                            "Set the Bridgehub pending admin",
+                            &tx_opts,
+                            &cfg,
+                        )
                         .await?
-                        .context("No transaction receipt for bridgehub admin acceptance")?
+                    };
+                let Some(transaction_receipt) = transaction_receipt else {
+                    return Ok(());
                 };
                 println!(
                     "New Bridgehub pending admin: {:?}",
@@ -106,23 +276,271 @@ impl Command {
             }
             Command::AcceptAdmin {
                 pending_admin_private_key,
+                tx_opts,
             } => {
-                let pending_admin =
-                    try_l1_signer_from_config(pending_admin_private_key, &cfg).await?;
-                // We need to instantiate a Bridgehub with the pending admin as the signer
-                // to be able to call accept_admin
-                let bridgehub = Bridgehub::new(bridgehub.address(), Arc::new(pending_admin));
-                let transaction_receipt = bridgehub
-                    .accept_admin()
-                    .send()
-                    .await?
-                    .await?
-                    .context("No transaction receipt for bridgehub admin acceptance")?;
+                // The pending admin has to be the signer to be able to call accept_admin
+                let pending_admin = try_l1_signer_from_config(
+                    pending_role_wallet(&cfg, "pending admin", pending_admin_private_key)?,
+                    &cfg,
+                )
+                .await?;
+                let Some(transaction_receipt) = send_l1_tx(
+                    &pending_admin,
+                    bridgehub.address(),
+                    bridgehub
+                        .accept_admin()
+                        .calldata()
+                        .context("Failed to encode the call")?,
+                    None,
+                    "Accept the Bridgehub admin",
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?
+                else {
+                    return Ok(());
+                };
                 println!(
                     "New Bridgehub admin accepted: {:?}",
                     transaction_receipt.transaction_hash
                 );
             }
+            Command::CreateNewChain {
+                chain_id,
+                state_transition_manager,
+                base_token,
+                salt,
+                admin,
+                init_data,
+                explorer_url,
+                tx_opts,
+            } => {
+                let init_data = Bytes::from_str(std::fs::read_to_string(&init_data)?.trim())
+                    .map_err(|e| {
+                        eyre::eyre!("Invalid init data in {}: {e}", init_data.display())
+                    })?;
+                let registered_state_transition_manager =
+                    bridgehub.state_transition_manager(chain_id).call().await?;
+                if !registered_state_transition_manager.is_zero() {
+                    eyre::bail!("Chain {chain_id} is already registered");
+                }
+                let calldata = bridgehub
+                    .create_new_chain(
+                        chain_id,
+                        state_transition_manager,
+                        base_token,
+                        salt,
+                        admin,
+                        init_data,
+                    )
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                let Some(transaction_receipt) = send_l1_tx(
+                    &bridgehub.client(),
+                    bridgehub.address(),
+                    calldata,
+                    cfg.bridgehub.owner_safe,
+                    &format!("Create chain {chain_id}"),
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?
+                else {
+                    return Ok(());
+                };
+                let new_chain_topic = H256::from(keccak256(NEW_CHAIN_EVENT));
+                let created = transaction_receipt.logs.iter().any(|log| {
+                    log.address == bridgehub.address()
+                        && log.topics.first() == Some(&new_chain_topic)
+                        && log
+                            .topics
+                            .get(1)
+                            .map(|topic| U256::from_big_endian(topic.as_bytes()))
+                            == Some(chain_id)
+                });
+                if !created {
+                    eyre::bail!(
+                        "No NewChain event for chain {chain_id} in {:?}",
+                        transaction_receipt.transaction_hash
+                    );
+                }
+                let hyperchain: Address = bridgehub.get_hyperchain(chain_id).call().await?;
+                println!("Chain {chain_id} created, its hyperchain contract is {hyperchain:?}");
+                print_transaction("Transaction", &transaction_receipt, explorer_url, &cfg)?;
+            }
+            Command::AddToken {
+                token,
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .add_token(token)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    &format!("Add token {token:?}"),
+                    "Token added",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::AddStateTransitionManager {
+                state_transition_manager,
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .add_state_transition_manager(state_transition_manager)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    &format!("Add StateTransitionManager {state_transition_manager:?}"),
+                    "StateTransitionManager added",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::RemoveStateTransitionManager {
+                state_transition_manager,
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .remove_state_transition_manager(state_transition_manager)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    &format!("Remove StateTransitionManager {state_transition_manager:?}"),
+                    "StateTransitionManager removed",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::SetSharedBridge {
+                shared_bridge,
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .set_shared_bridge(shared_bridge)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    &format!("Set the shared bridge to {shared_bridge:?}"),
+                    "Shared bridge set",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::Pause {
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .pause()
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    "Pause the Bridgehub",
+                    "Bridgehub paused",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::Unpause {
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .unpause()
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    "Unpause the Bridgehub",
+                    "Bridgehub unpaused",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::TransferOwnership {
+                new_owner,
+                explorer_url,
+                tx_opts,
+            } => {
+                let calldata = bridgehub
+                    .transfer_ownership(new_owner)
+                    .calldata()
+                    .context("Failed to encode the call")?;
+                send_owner_tx(
+                    &bridgehub,
+                    calldata,
+                    &format!("Transfer the Bridgehub ownership to {new_owner:?}"),
+                    "Bridgehub pending owner set, it has to accept the ownership",
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
+            }
+            Command::AcceptOwnership {
+                pending_owner_private_key,
+                explorer_url,
+                tx_opts,
+            } => {
+                // The pending owner has to be the signer to be able to call accept_ownership
+                let pending_owner = try_l1_signer_from_config(
+                    pending_role_wallet(&cfg, "pending owner", pending_owner_private_key)?,
+                    &cfg,
+                )
+                .await?;
+                let Some(transaction_receipt) = send_l1_tx(
+                    &pending_owner,
+                    bridgehub.address(),
+                    bridgehub
+                        .accept_ownership()
+                        .calldata()
+                        .context("Failed to encode the call")?,
+                    None,
+                    "Accept the Bridgehub ownership",
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?
+                else {
+                    return Ok(());
+                };
+                print_transaction(
+                    "Bridgehub ownership accepted",
+                    &transaction_receipt,
+                    explorer_url,
+                    &cfg,
+                )?;
+            }
+            Command::Chains { .. } => unreachable!(),
             Command::GetHyperchain { chain_id } => {
                 let hyperchain: Address = bridgehub.get_hyperchain(chain_id).call().await?;
                 println!("Hyperchain address for chain ID {chain_id:?}: {hyperchain:?}");
@@ -131,3 +549,184 @@ impl Command {
         Ok(())
     }
 }
+
+/// Sends a transaction of the Bridgehub owner, through its Safe if configured, once confirmed.
+async fn send_owner_tx<M: Middleware + 'static, S: Signer + 'static>(
+    bridgehub: &Bridgehub<SignerMiddleware<M, S>>,
+    calldata: Bytes,
+    action: &str,
+    done_message: &str,
+    explorer_url: bool,
+    tx_opts: &TxOpts,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<()> {
+    let Some(transaction_receipt) = send_l1_tx(
+        &bridgehub.client(),
+        bridgehub.address(),
+        calldata,
+        cfg.bridgehub.owner_safe,
+        action,
+        tx_opts,
+        cfg,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+    print_transaction(done_message, &transaction_receipt, explorer_url, cfg)
+}
+
+/// Prints the transaction hash, or its L1 explorer link if asked to.
+fn print_transaction(
+    message: &str,
+    transaction_receipt: &TransactionReceipt,
+    explorer_url: bool,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<()> {
+    if explorer_url {
+        let url = cfg
+            .network
+            .l1_explorer_url
+            .as_ref()
+            .context("L1 Explorer URL missing in config")?;
+        println!(
+            "{message}: {url}/tx/{:?}",
+            transaction_receipt.transaction_hash
+        );
+    } else {
+        println!("{message}: {:?}", transaction_receipt.transaction_hash);
+    }
+    Ok(())
+}
+
+/// Registered chain, as shown by `bridgehub chains`.
+struct ChainOverview {
+    chain_id: U256,
+    state_transition_manager: Address,
+    base_token: Address,
+    base_token_symbol: String,
+    hyperchain: Address,
+    /// Missing if the chain has no hyperchain deployed.
+    admin: Option<Address>,
+    protocol_version: Option<U256>,
+}
+
+async fn list_chains(
+    from_block: u64,
+    to_block: Option<u64>,
+    json: bool,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<()> {
+    let l1_provider = try_l1_provider_from_config(cfg)?;
+    let bridgehub_address = bridgehub_address(cfg).await?;
+    let bridgehub = Bridgehub::new(bridgehub_address, Arc::new(l1_provider.clone()));
+    let filter = Filter::new()
+        .address(bridgehub_address)
+        .topic0(H256::from(keccak256(NEW_CHAIN_EVENT)));
+    let logs = get_logs_in_range(filter, from_block, to_block, &l1_provider).await?;
+
+    let mut chains = Vec::new();
+    for log in logs {
+        let Some(chain_id) = log.topics.get(1) else {
+            continue;
+        };
+        let chain_id = U256::from_big_endian(chain_id.as_bytes());
+        let base_token = bridgehub.base_token(chain_id).call().await?;
+        let base_token_symbol = if base_token == ETH_TOKEN_ADDRESS {
+            "ETH".to_owned()
+        } else {
+            get_erc20_decimals_symbol(base_token, &l1_provider)
+                .await
+                .map(|(_, symbol)| symbol)
+                .unwrap_or_else(|_| "?".to_owned())
+        };
+        let hyperchain = bridgehub.get_hyperchain(chain_id).call().await?;
+        let (admin, protocol_version) = if hyperchain.is_zero() {
+            (None, None)
+        } else {
+            let admin = hyperchain_view(hyperchain, "getAdmin()", &l1_provider).await?;
+            let protocol_version =
+                hyperchain_view(hyperchain, "getProtocolVersion()", &l1_provider).await?;
+            (
+                Some(admin.into()),
+                Some(U256::from_big_endian(protocol_version.as_bytes())),
+            )
+        };
+        chains.push(ChainOverview {
+            chain_id,
+            state_transition_manager: bridgehub.state_transition_manager(chain_id).call().await?,
+            base_token,
+            base_token_symbol,
+            hyperchain,
+            admin,
+            protocol_version,
+        });
+    }
+
+    if json {
+        let chains: Vec<serde_json::Value> = chains
+            .iter()
+            .map(|chain| {
+                serde_json::json!({
+                    "chain_id": chain.chain_id.to_string(),
+                    "state_transition_manager": chain.state_transition_manager,
+                    "base_token": chain.base_token,
+                    "base_token_symbol": chain.base_token_symbol,
+                    "hyperchain": chain.hyperchain,
+                    "admin": chain.admin,
+                    "protocol_version": chain.protocol_version.map(format_protocol_version),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&chains)?);
+        return Ok(());
+    }
+    println!(
+        "| {:<12} | {:<42} | {:<42} | {:<8} | {:<42} | {:<42} | {:<8} |",
+        "Chain ID", "STM", "Base token", "Symbol", "Hyperchain", "Admin", "Protocol"
+    );
+    for chain in chains {
+        println!(
+            "| {:<12} | {:<42} | {:<42} | {:<8} | {:<42} | {:<42} | {:<8} |",
+            chain.chain_id,
+            format!("{:?}", chain.state_transition_manager),
+            format!("{:?}", chain.base_token),
+            chain.base_token_symbol,
+            format!("{:?}", chain.hyperchain),
+            chain
+                .admin
+                .map_or_else(|| "-".to_owned(), |admin| format!("{admin:?}")),
+            chain
+                .protocol_version
+                .map_or_else(|| "-".to_owned(), format_protocol_version)
+        );
+    }
+    Ok(())
+}
+
+/// Calls a hyperchain getter without arguments, returning its first word.
+async fn hyperchain_view(
+    hyperchain: Address,
+    function_signature: &str,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<H256> {
+    let call: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(hyperchain)
+        .data(Bytes::from(get_fn_selector(function_signature).to_vec()))
+        .into();
+    let result = l1_provider.call(&call, None).await?;
+    result
+        .get(..32)
+        .map(H256::from_slice)
+        .ok_or_else(|| eyre::eyre!("{function_signature} of {hyperchain:?} returned no value"))
+}
+
+/// Semantic protocol versions pack the minor version above the 32 bits patch.
+fn format_protocol_version(protocol_version: U256) -> String {
+    let minor = protocol_version >> 32_u32;
+    if minor.is_zero() {
+        protocol_version.to_string()
+    } else {
+        format!("0.{minor}.{}", protocol_version.low_u32())
+    }
+}
@@ -1,12 +1,31 @@
-use crate::{config::ZKSyncConfig, utils::contracts::try_governance_from_config};
+use crate::{
+    commands::utils::encode_calldata,
+    config::ZKSyncConfig,
+    utils::{
+        contracts::{try_governance_from_config, try_governance_security_council_from_config},
+        governance::{decode_call, format_duration, scan_operations, simulate_call},
+        try_l1_provider_from_config,
+        tx::{send_l1_tx, TxOpts},
+    },
+};
+use chrono::DateTime;
 use clap::Subcommand;
+use colored::Colorize;
 use eyre::{Context, ContextCompat};
-use std::str::FromStr;
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 use zksync_ethers_rs::{
-    abi::{Hash, Tokenize},
+    abi::{encode, Hash, Tokenizable, Tokenize},
     contracts::governance::{Call, Governance, Operation},
-    providers::Middleware,
-    types::{Address, Bytes, U256},
+    core::utils::keccak256,
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{Address, BlockNumber, Bytes, H256, U256},
 };
 
 #[derive(Subcommand)]
@@ -27,6 +46,32 @@ pub(crate) enum Command {
     IsOperationDone { operation_id: Hash },
     #[clap(about = "Returns the state of an operation.")]
     OperationState { operation_id: Hash },
+    #[clap(
+        about = "Lists the scheduled operations with their state, timelock countdown and calls.",
+        visible_alias = "ops"
+    )]
+    Operations {
+        #[clap(
+            long,
+            required = true,
+            help = "Block to scan the events from, e.g. the Governance deployment block"
+        )]
+        from_block: u64,
+        #[clap(long, required = false, help = "Defaults to the latest block")]
+        to_block: Option<u64>,
+        #[clap(
+            long,
+            required = false,
+            help = "Only list the operations not executed nor cancelled"
+        )]
+        pending: bool,
+        #[clap(
+            long = "signature",
+            value_name = "SIGNATURE",
+            help = "Function signature to decode the calls with, e.g. setValidator(address,bool)"
+        )]
+        signatures: Vec<String>,
+    },
     #[clap(
         about = "Propose an upgrade, this could be fully transparent providing upgrade data on-chain, or a \"shadow\" upgrade not publishing data on-chain. Only the current owner can propose a shadow upgrade."
     )]
@@ -43,17 +88,55 @@ pub(crate) enum Command {
         operation: Option<Operation>,
         #[clap(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
     #[clap(about = "Cancel a scheduled operation.")]
-    Cancel { operation_id: Hash },
+    Cancel {
+        operation_id: Hash,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
     #[clap(about = "Execute a scheduled operation.")]
     Execute {
         #[clap(value_parser = parse_operation)]
         operation: Operation,
-        #[arg(short = 'i', long, required = false)]
+        #[arg(
+            short = 'i',
+            long,
+            required = false,
+            help = "Execute it before its delay passed, signing as the security council"
+        )]
         instant: bool,
         #[arg(short = 'e', long, required = false)]
         explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(
+        subcommand,
+        about = "Governance operation files commands.",
+        visible_alias = "op"
+    )]
+    Operation(OperationCommand),
+    #[clap(
+        about = "Decodes the calls of an operation and simulates them from the Governance contract, reporting the ones that revert."
+    )]
+    Simulate {
+        #[clap(value_parser = parse_operation)]
+        operation: Operation,
+        #[clap(
+            long,
+            required = false,
+            help = "L1 RPC to simulate against, e.g. a local fork, defaults to the configured one"
+        )]
+        l1_rpc_url: Option<String>,
+        #[clap(
+            long = "signature",
+            value_name = "SIGNATURE",
+            help = "Function signature to decode the calls with, e.g. setValidator(address,bool)"
+        )]
+        signatures: Vec<String>,
     },
     #[clap(about = "Get the hash of an operation.")]
     HashOperation {
@@ -73,10 +156,20 @@ pub(crate) enum Command {
         shadow_upgrade: bool,
         #[arg(short = 't', long, required_unless_present = "shadow_upgrade")]
         transparent_upgrade: bool,
-        #[arg(short = 'e', long, required = false)]
+        #[arg(long, required = false)]
         execute: bool,
+        #[arg(
+            short = 'i',
+            long,
+            requires = "execute",
+            required = false,
+            help = "Execute it right away, signing as the security council"
+        )]
+        instant: bool,
         #[arg(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
     #[clap(
         about = "Updates the address of the security council.",
@@ -91,13 +184,67 @@ pub(crate) enum Command {
         shadow_upgrade: bool,
         #[arg(short = 't', long, required_unless_present = "shadow_upgrade")]
         transparent_upgrade: bool,
-        #[arg(short = 'e', long, required = false)]
+        #[arg(long, required = false)]
         execute: bool,
+        #[arg(
+            short = 'i',
+            long,
+            requires = "execute",
+            required = false,
+            help = "Execute it right away, signing as the security council"
+        )]
+        instant: bool,
         #[arg(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum OperationCommand {
+    #[clap(
+        about = "Build an operation from its calls. The written file can be given to the commands taking an operation as @FILE."
+    )]
+    Build {
+        #[clap(
+            long = "call",
+            value_name = "CALL",
+            value_parser = parse_call,
+            required = true,
+            help = "\"TARGET[:VALUE] SIGNATURE [ARG]...\", e.g. \"0x1234...:0 setPendingAdmin(address) 0x5678...\", or \"TARGET[:VALUE] CALLDATA\""
+        )]
+        calls: Vec<Call>,
+        #[clap(long, required = false)]
+        predecessor: Option<H256>,
+        #[clap(long, required = false)]
+        salt: Option<H256>,
+        #[clap(long, short = 'o', value_name = "FILE", required = false)]
+        out: Option<PathBuf>,
     },
 }
 
+impl OperationCommand {
+    pub fn run(self) -> eyre::Result<()> {
+        match self {
+            OperationCommand::Build {
+                calls,
+                predecessor,
+                salt,
+                out,
+            } => {
+                let operation = Operation {
+                    calls,
+                    predecessor: predecessor.unwrap_or_default().0,
+                    salt: salt.unwrap_or_default().0,
+                };
+                write_operation(&operation, out.as_deref())?;
+            }
+        };
+        Ok(())
+    }
+}
+
 #[derive(Debug)]
 enum OperationState {
     Unset,
@@ -106,6 +253,18 @@ enum OperationState {
     Done,
 }
 
+impl Display for OperationState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            OperationState::Unset => "Unset".normal(),
+            OperationState::Waiting => "Waiting".yellow(),
+            OperationState::Ready => "Ready".green(),
+            OperationState::Done => "Done".blue(),
+        };
+        write!(f, "{state}")
+    }
+}
+
 impl From<u8> for OperationState {
     fn from(value: u8) -> Self {
         match value {
@@ -120,6 +279,36 @@ impl From<u8> for OperationState {
 
 impl Command {
     pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
+        if let Command::Operation(cmd) = self {
+            return cmd.run();
+        }
+        if let Command::Operations {
+            from_block,
+            to_block,
+            pending,
+            signatures,
+        } = self
+        {
+            return list_operations(from_block, to_block, pending, &signatures, &cfg).await;
+        }
+        if let Command::Simulate {
+            operation,
+            l1_rpc_url,
+            signatures,
+        } = self
+        {
+            let l1_provider = match l1_rpc_url {
+                Some(l1_rpc_url) => Provider::try_from(l1_rpc_url.as_str())?,
+                None => try_l1_provider_from_config(&cfg)?,
+            };
+            return simulate_operation(
+                &operation,
+                cfg.governance.address,
+                &signatures,
+                &l1_provider,
+            )
+            .await;
+        }
         let governance = try_governance_from_config(&cfg).await?;
         match self {
             Command::IsOperation { operation_id } => {
@@ -189,55 +378,54 @@ impl Command {
                 transparent,
                 operation,
                 explorer_url,
+                tx_opts,
             } => {
-                let transaction_receipt = if shadow {
-                    governance
-                        .schedule_shadow(
-                            operation_id
-                                .context("--operation-id is required with --shadow")?
-                                .into(),
-                            delay,
-                        )
-                        .send()
-                        .await?
-                        .await?
-                        .context("No transaction receipt for shadow operation")?
+                let schedule_call = if shadow {
+                    governance.schedule_shadow(
+                        operation_id
+                            .context("--operation-id is required with --shadow")?
+                            .into(),
+                        delay,
+                    )
                 } else if transparent {
-                    governance
-                        .schedule_transparent(
-                            operation.context("--operation is required with --transparent")?,
-                            delay,
-                        )
-                        .send()
-                        .await?
-                        .await?
-                        .context("No transaction receipt for transparent operation")?
+                    governance.schedule_transparent(
+                        operation.context("--operation is required with --transparent")?,
+                        delay,
+                    )
                 } else {
                     eyre::bail!("Either --shadow or --transparent must be provided");
                 };
-                if explorer_url {
-                    let url = cfg
-                        .network
-                        .l1_explorer_url
-                        .context("L1 Explorer URL missing in config")?;
-                    println!(
-                        "Upgrade scheduled: {url}/tx/{:?}",
-                        transaction_receipt.transaction_hash
-                    );
-                } else {
-                    println!(
-                        "Upgrade scheduled: {:?}",
-                        transaction_receipt.transaction_hash
-                    );
-                }
+                schedule_operation(
+                    &governance,
+                    schedule_call
+                        .calldata()
+                        .context("Failed to encode the call")?,
+                    explorer_url,
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?;
             }
-            Command::Cancel { operation_id } => {
-                let transaction_receipt = governance
-                    .cancel(operation_id.into())
-                    .send()
-                    .await?
-                    .await?
-                    .context("No transaction receipt for operation cancel")?;
+            Command::Cancel {
+                operation_id,
+                tx_opts,
+            } => {
+                let cancel_call = governance.cancel(operation_id.into());
+                let Some(transaction_receipt) = send_l1_tx(
+                    &governance.client(),
+                    governance.address(),
+                    cancel_call
+                        .calldata()
+                        .context("Failed to encode the call")?,
+                    cfg.governance.owner_safe,
+                    "Cancel the operation",
+                    &tx_opts,
+                    &cfg,
+                )
+                .await?
+                else {
+                    return Ok(());
+                };
                 println!(
                     "Operation canceled: {:?}",
                     transaction_receipt.transaction_hash
@@ -247,38 +435,49 @@ impl Command {
                 operation,
                 instant,
                 explorer_url,
+                tx_opts,
             } => {
-                let transaction_receipt = if instant {
-                    governance
-                        .execute_instant(operation)
-                        .send()
-                        .await?
-                        .await?
-                        .context("No transaction receipt for operation execution")?
-                } else {
-                    governance
-                        .execute(operation)
-                        .send()
-                        .await?
-                        .await?
-                        .context("No transaction receipt for operation execution")?
-                };
-                if explorer_url {
-                    let url = cfg
-                        .network
-                        .l1_explorer_url
-                        .context("L1 Explorer URL missing in config")?;
-                    println!(
-                        "Upgrade executed: {url}/tx/{:?}",
-                        transaction_receipt.transaction_hash
-                    );
+                if instant {
+                    let security_council_governance =
+                        try_governance_security_council_from_config(&cfg).await?;
+                    if tx_opts.sign_opts.sign_only.is_none() {
+                        let security_council = security_council_governance
+                            .security_council()
+                            .call()
+                            .await?;
+                        let signer = security_council_governance.client().address();
+                        if signer != security_council {
+                            eyre::bail!(
+                                "{signer:?} is not the security council {security_council:?}, set governance.security_council_private_key or governance.security_council_wallet in the config"
+                            );
+                        }
+                    }
+                    execute_operation(
+                        &security_council_governance,
+                        operation,
+                        true,
+                        None,
+                        explorer_url,
+                        &tx_opts,
+                        &cfg,
+                    )
+                    .await?;
                 } else {
-                    println!(
-                        "Upgrade executed: {:?}",
-                        transaction_receipt.transaction_hash
-                    );
+                    execute_operation(
+                        &governance,
+                        operation,
+                        false,
+                        cfg.governance.owner_safe,
+                        explorer_url,
+                        &tx_opts,
+                        &cfg,
+                    )
+                    .await?;
                 }
             }
+            Command::Operation(_) | Command::Operations { .. } | Command::Simulate { .. } => {
+                unreachable!()
+            }
             Command::HashOperation { operation } => {
                 let hashed_operation: Hash =
                     governance.hash_operation(operation).call().await?.into();
@@ -290,7 +489,9 @@ impl Command {
                 shadow_upgrade,
                 transparent_upgrade,
                 execute,
+                instant,
                 explorer_url,
+                tx_opts,
             } => {
                 // Prepare the security council update operation
                 let update_delay_calldata = governance
@@ -301,8 +502,10 @@ impl Command {
                     update_delay_calldata.into(),
                     shadow_upgrade || !transparent_upgrade,
                     execute,
+                    instant,
                     delay,
                     explorer_url,
+                    tx_opts,
                     governance,
                     cfg,
                 )
@@ -314,7 +517,9 @@ impl Command {
                 shadow_upgrade,
                 transparent_upgrade,
                 execute,
+                instant,
                 explorer_url,
+                tx_opts,
             } => {
                 let update_security_council_calldata = governance
                     .update_security_council(new_security_council)
@@ -324,8 +529,10 @@ impl Command {
                     update_security_council_calldata.into(),
                     shadow_upgrade || !transparent_upgrade,
                     execute,
+                    instant,
                     delay,
                     explorer_url,
+                    tx_opts,
                     governance,
                     cfg,
                 )
@@ -336,8 +543,135 @@ impl Command {
     }
 }
 
+async fn list_operations(
+    from_block: u64,
+    to_block: Option<u64>,
+    pending: bool,
+    signatures: &[String],
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<()> {
+    let l1_provider = try_l1_provider_from_config(cfg)?;
+    let governance = Governance::new(cfg.governance.address, Arc::new(l1_provider.clone()));
+    let operations =
+        scan_operations(governance.address(), from_block, to_block, &l1_provider).await?;
+    let now = l1_provider
+        .get_block(BlockNumber::Latest)
+        .await?
+        .context("Latest block not found")?
+        .timestamp
+        .as_u64();
+    let min_delay = governance.min_delay().call().await?;
+    println!(
+        "Min delay: {}",
+        format_duration(u64::try_from(min_delay).unwrap_or(u64::MAX))
+    );
+
+    for scheduled in operations {
+        let id: [u8; 32] = scheduled.id.into();
+        let state: OperationState = governance.get_operation_state(id).call().await?.into();
+        let is_pending = scheduled.executed_tx_hash.is_none()
+            && scheduled.cancelled_tx_hash.is_none()
+            && !matches!(state, OperationState::Done);
+        if pending && !is_pending {
+            continue;
+        }
+
+        println!();
+        if scheduled.cancelled_tx_hash.is_some() {
+            println!("{:?} {}", scheduled.id, "Cancelled".red());
+        } else {
+            println!("{:?} {state}", scheduled.id);
+        }
+        println!(
+            "  Scheduled: {} in {:?}",
+            scheduled.scheduled_at,
+            scheduled.scheduled_tx_hash.unwrap_or_default()
+        );
+        println!(
+            "  Delay: {}",
+            format_duration(u64::try_from(scheduled.delay).unwrap_or(u64::MAX))
+        );
+        // The timestamp is reset when the operation is cancelled and set to 1 once executed.
+        let ready_at = u64::try_from(governance.timestamps(id).call().await?).unwrap_or(u64::MAX);
+        match state {
+            OperationState::Waiting => println!(
+                "  Ready in {} at {}",
+                format_duration(ready_at.saturating_sub(now)),
+                DateTime::from_timestamp(ready_at.try_into()?, 0).unwrap_or_default()
+            ),
+            OperationState::Ready => println!(
+                "  Ready since {}",
+                DateTime::from_timestamp(ready_at.try_into()?, 0).unwrap_or_default()
+            ),
+            OperationState::Unset | OperationState::Done => {}
+        }
+        if let Some(executed_tx_hash) = scheduled.executed_tx_hash {
+            println!("  Executed in {executed_tx_hash:?}");
+        }
+        if let Some(cancelled_tx_hash) = scheduled.cancelled_tx_hash {
+            println!("  Cancelled in {cancelled_tx_hash:?}");
+        }
+        match scheduled.operation {
+            Some(operation) => {
+                println!("  Predecessor: {:?}", H256::from(operation.predecessor));
+                println!("  Salt: {:?}", H256::from(operation.salt));
+                println!("  Calls:");
+                for call in operation.calls {
+                    println!(
+                        "    {:?} (value {}): {}",
+                        call.target,
+                        call.value,
+                        decode_call(&call.data, signatures)
+                    );
+                }
+            }
+            None => println!("  Shadow operation, its calls are not published"),
+        }
+    }
+    Ok(())
+}
+
+/// Simulates the calls of an operation one by one, so each of them runs against the current
+/// state rather than the one left by the previous calls.
+async fn simulate_operation(
+    operation: &Operation,
+    governance: Address,
+    signatures: &[String],
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<()> {
+    println!("Operation hash: {:?}", hash_operation(operation));
+    let mut reverted_calls = 0_usize;
+    for (i, call) in operation.calls.iter().enumerate() {
+        println!(
+            "Call {i} to {:?} (value {}): {}",
+            call.target,
+            call.value,
+            decode_call(&call.data, signatures)
+        );
+        match simulate_call(governance, call, l1_provider).await? {
+            Some(revert_reason) => {
+                reverted_calls += 1;
+                println!("  {} {revert_reason}", "Reverts:".red());
+            }
+            None => println!("  {}", "Succeeds".green()),
+        }
+    }
+    if reverted_calls > 0 {
+        eyre::bail!("{reverted_calls} of {} calls revert", operation.calls.len());
+    }
+    Ok(())
+}
+
+/// Parses an operation JSON, or the JSON file at `@path`:
+/// `{"calls": [{"target": ..., "value": ..., "data": ...}], "predecessor": ..., "salt": ...}`.
+/// The values are numbers or decimal or hex strings, the predecessor and salt default to zero.
 pub(crate) fn parse_operation(raw_operation: &str) -> eyre::Result<Operation> {
-    let raw_operation = serde_json::Value::from_str(raw_operation).context("Invalid JSON")?;
+    let raw_operation = match raw_operation.strip_prefix('@') {
+        Some(path) => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read the operation file {path}"))?,
+        None => raw_operation.to_owned(),
+    };
+    let raw_operation = serde_json::Value::from_str(&raw_operation).context("Invalid JSON")?;
     let calls = raw_operation
         .get("calls")
         .context("No \"calls\" in operation")?
@@ -346,40 +680,260 @@ pub(crate) fn parse_operation(raw_operation: &str) -> eyre::Result<Operation> {
         .iter()
         .map(|raw_call| {
             let target = raw_call.get("target").context("No target in call")?;
-            let value = raw_call
-                .get("value")
-                .context("No \"value\" in call")?
-                .as_u64()
-                .context("\"value\" is not a number")?;
+            let value = parse_value(raw_call.get("value").context("No \"value\" in call")?)?;
             let data = raw_call.get("data").context("No data in call")?;
             Ok(Call {
                 target: serde_json::from_value(target.clone())?,
-                value: value.into(),
+                value,
                 data: serde_json::from_value(data.clone())?,
             })
         })
         .collect::<Result<Vec<Call>, eyre::Error>>()?;
-    // TODO: parse predecessor and salt
-    let predecessor = [0_u8; 32];
-    let salt = [0_u8; 32];
+    let parse_hash = |field: &str| -> eyre::Result<[u8; 32]> {
+        match raw_operation.get(field) {
+            Some(raw_hash) => Ok(serde_json::from_value::<H256>(raw_hash.clone())
+                .with_context(|| format!("\"{field}\" is not a 32 bytes hex string"))?
+                .0),
+            None => Ok([0_u8; 32]),
+        }
+    };
     let parsed_operation = Operation {
         calls,
-        predecessor,
-        salt,
+        predecessor: parse_hash("predecessor")?,
+        salt: parse_hash("salt")?,
     };
     Ok(parsed_operation)
 }
 
-pub(crate) async fn run_upgrade(
+fn parse_value(raw_value: &serde_json::Value) -> eyre::Result<U256> {
+    if let Some(value) = raw_value.as_u64() {
+        return Ok(value.into());
+    }
+    let value = raw_value
+        .as_str()
+        .context("\"value\" is not a number nor a string")?;
+    parse_u256(value)
+}
+
+/// Parses a decimal or `0x` prefixed hex number.
+pub(crate) fn parse_u256(value: &str) -> eyre::Result<U256> {
+    match value.strip_prefix("0x") {
+        Some(hex_value) => U256::from_str_radix(hex_value, 16)
+            .with_context(|| format!("Invalid hex value {value}")),
+        None => U256::from_dec_str(value).with_context(|| format!("Invalid value {value}")),
+    }
+}
+
+/// Splits a call on the whitespace outside of double quotes, brackets and parentheses, so
+/// string, array and tuple arguments can contain spaces. The quotes around an argument are
+/// removed, the ones inside an array or tuple are kept.
+fn split_call(raw_call: &str) -> eyre::Result<Vec<String>> {
+    let mut parts = Vec::new();
+    let mut part: Option<String> = None;
+    let mut depth = 0_usize;
+    let mut quoted = false;
+    for c in raw_call.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                let part = part.get_or_insert_with(String::new);
+                if depth > 0 {
+                    part.push(c);
+                }
+            }
+            '[' | '(' if !quoted => {
+                depth += 1;
+                part.get_or_insert_with(String::new).push(c);
+            }
+            ']' | ')' if !quoted => {
+                depth = depth
+                    .checked_sub(1)
+                    .with_context(|| format!("Unbalanced {c} in call {raw_call}"))?;
+                part.get_or_insert_with(String::new).push(c);
+            }
+            c if c.is_whitespace() && !quoted && depth == 0 => parts.extend(part.take()),
+            c => part.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quoted || depth > 0 {
+        eyre::bail!("Unclosed quote or bracket in call {raw_call}");
+    }
+    parts.extend(part);
+    Ok(parts)
+}
+
+/// Parses a `TARGET[:VALUE] SIGNATURE [ARG]...` or `TARGET[:VALUE] CALLDATA` call.
+fn parse_call(raw_call: &str) -> eyre::Result<Call> {
+    let mut parts = split_call(raw_call)?.into_iter();
+    let target = parts.next().context("Empty call")?;
+    let (target, value) = match target.split_once(':') {
+        Some((target, value)) => (target, parse_u256(value)?),
+        None => (target.as_str(), U256::zero()),
+    };
+    let function = parts.next().context("No function signature in call")?;
+    let args: Vec<String> = parts.collect();
+    let data = if function.starts_with("0x") {
+        if !args.is_empty() {
+            eyre::bail!("Calldata calls take no arguments");
+        }
+        Bytes::from_str(&function)?
+    } else {
+        encode_calldata(&function, &args)?.into()
+    };
+    Ok(Call {
+        target: target.parse()?,
+        value,
+        data,
+    })
+}
+
+/// The JSON of an operation, as read by [`parse_operation`].
+pub(crate) fn operation_to_json(operation: &Operation) -> serde_json::Value {
+    let calls: Vec<serde_json::Value> = operation
+        .calls
+        .iter()
+        .map(|call| {
+            serde_json::json!({
+                "target": call.target,
+                "value": call.value,
+                "data": call.data,
+            })
+        })
+        .collect();
+    serde_json::json!({
+        "calls": calls,
+        "predecessor": H256::from(operation.predecessor),
+        "salt": H256::from(operation.salt),
+    })
+}
+
+/// Writes the operation JSON to `out`, or prints it, along with its hash. The hash goes to
+/// stderr when the JSON is printed, so the output can be piped into a file.
+pub(crate) fn write_operation(operation: &Operation, out: Option<&Path>) -> eyre::Result<()> {
+    let operation_json = serde_json::to_string_pretty(&operation_to_json(operation))?;
+    if let Some(out) = out {
+        std::fs::write(out, operation_json)?;
+        println!("Operation written to {}", out.display());
+        println!("Operation hash: {:?}", hash_operation(operation));
+    } else {
+        println!("{operation_json}");
+        eprintln!("Operation hash: {:?}", hash_operation(operation));
+    }
+    Ok(())
+}
+
+/// The operation id, as computed by `Governance.hashOperation`.
+pub(crate) fn hash_operation(operation: &Operation) -> H256 {
+    keccak256(encode(&[operation.clone().into_token()])).into()
+}
+
+/// Sends the schedule call, returns whether it was sent.
+async fn schedule_operation<M: Middleware + 'static, S: Signer + 'static>(
+    governance: &Governance<SignerMiddleware<M, S>>,
+    schedule_calldata: Bytes,
+    explorer_url: bool,
+    tx_opts: &TxOpts,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<bool> {
+    let Some(transaction_receipt) = send_l1_tx(
+        &governance.client(),
+        governance.address(),
+        schedule_calldata,
+        cfg.governance.owner_safe,
+        "Propose the upgrade",
+        tx_opts,
+        cfg,
+    )
+    .await?
+    else {
+        return Ok(false);
+    };
+    if explorer_url {
+        let url = cfg
+            .network
+            .l1_explorer_url
+            .as_ref()
+            .context("L1 Explorer URL missing in config")?;
+        println!(
+            "Upgrade scheduled: {url}/tx/{:?}",
+            transaction_receipt.transaction_hash
+        );
+    } else {
+        println!(
+            "Upgrade scheduled: {:?}",
+            transaction_receipt.transaction_hash
+        );
+    }
+    Ok(true)
+}
+
+async fn execute_operation<M: Middleware + 'static, S: Signer + 'static>(
+    governance: &Governance<SignerMiddleware<M, S>>,
+    operation: Operation,
+    instant: bool,
+    safe: Option<Address>,
+    explorer_url: bool,
+    tx_opts: &TxOpts,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<()> {
+    let execute_call = if instant {
+        governance.execute_instant(operation)
+    } else {
+        governance.execute(operation)
+    };
+    let Some(transaction_receipt) = send_l1_tx(
+        &governance.client(),
+        governance.address(),
+        execute_call
+            .calldata()
+            .context("Failed to encode the call")?,
+        safe,
+        if instant {
+            "Execute the operation instantly"
+        } else {
+            "Execute the operation"
+        },
+        tx_opts,
+        cfg,
+    )
+    .await?
+    else {
+        return Ok(());
+    };
+    if explorer_url {
+        let url = cfg
+            .network
+            .l1_explorer_url
+            .as_ref()
+            .context("L1 Explorer URL missing in config")?;
+        println!(
+            "Upgrade executed: {url}/tx/{:?}",
+            transaction_receipt.transaction_hash
+        );
+    } else {
+        println!(
+            "Upgrade executed: {:?}",
+            transaction_receipt.transaction_hash
+        );
+    }
+    Ok(())
+}
+
+pub(crate) async fn run_upgrade<M: Middleware + 'static, S: Signer + 'static>(
     calldata: Bytes,
     is_shadow_upgrade: bool,
     execute_upgrade: bool,
+    execute_instant: bool,
     delay: U256,
     explorer_url: bool,
-    governance: Governance<impl Middleware + 'static>,
+    tx_opts: TxOpts,
+    governance: Governance<SignerMiddleware<M, S>>,
     cfg: ZKSyncConfig,
 ) -> eyre::Result<()> {
     Box::pin(async {
+        if execute_upgrade && tx_opts.sign_opts.sign_only.is_some() {
+            eyre::bail!("--sign-only can only propose the upgrade, execute it once it is ready");
+        }
         let call = Call {
             target: governance.address(),
             value: U256::zero(),
@@ -393,23 +947,34 @@ pub(crate) async fn run_upgrade(
         let operation_hash = governance.hash_operation(operation.clone()).call().await?;
 
         // Propose the new security council update
-        if is_shadow_upgrade {
-            propose_shadow_upgrade(operation_hash, delay, explorer_url, cfg.clone()).await?;
+        let schedule_call = if is_shadow_upgrade {
+            governance.schedule_shadow(operation_hash, delay)
         } else {
-            propose_transparent_upgrade(operation.clone(), delay, explorer_url, cfg.clone())
-                .await?;
-        }
+            governance.schedule_transparent(operation.clone(), delay)
+        };
+        let proposed = schedule_operation(
+            &governance,
+            schedule_call
+                .calldata()
+                .context("Failed to encode the call")?,
+            explorer_url,
+            &tx_opts,
+            &cfg,
+        )
+        .await?;
 
-        // Execute the new security council update if wanted
+        // Execute the upgrade if wanted, instantly as the security council if asked to.
+        // Nothing was scheduled if the proposal was declined or only signed for the Safe.
         if execute_upgrade {
-            // TODO: make instant execution an option. This would require
-            // the current security council private key to be passed in, as
-            // the current security council is the only one that can execute
-            // the operation instantly.
+            if !proposed {
+                println!("The upgrade was not scheduled, skipping its execution");
+                return Ok(());
+            }
             Command::Execute {
                 operation,
-                instant: false,
+                instant: execute_instant,
                 explorer_url,
+                tx_opts,
             }
             .run(cfg)
             .await?;
@@ -419,38 +984,150 @@ pub(crate) async fn run_upgrade(
     .await
 }
 
-async fn propose_shadow_upgrade(
-    operation_hash: [u8; 32],
-    delay: U256,
-    explorer_url: bool,
-    cfg: ZKSyncConfig,
-) -> eyre::Result<()> {
-    Command::ProposeUpgrade {
-        shadow: true,
-        operation_id: Some(operation_hash.into()),
-        delay,
-        transparent: false,
-        operation: None,
-        explorer_url,
+#[cfg(test)]
+mod tests {
+    use super::{hash_operation, operation_to_json, parse_call, parse_operation};
+    use crate::utils::contract::get_fn_selector;
+    use std::str::FromStr;
+    use zksync_ethers_rs::{
+        abi::{encode, Token},
+        contracts::governance::{Call, Operation},
+        types::{Address, Bytes, H256, U256},
+    };
+
+    fn operation() -> eyre::Result<Operation> {
+        Ok(Operation {
+            calls: vec![
+                Call {
+                    target: Address::from_str("0x1111111111111111111111111111111111111111")?,
+                    value: U256::zero(),
+                    data: Bytes::from_str("0x12345678")?,
+                },
+                Call {
+                    target: Address::from_str("0x2222222222222222222222222222222222222222")?,
+                    value: U256::exp10(18_usize),
+                    data: Bytes::new(),
+                },
+            ],
+            predecessor: [0_u8; 32],
+            salt: H256::from_low_u64_be(1_u64).0,
+        })
     }
-    .run(cfg)
-    .await
-}
 
-async fn propose_transparent_upgrade(
-    operation: Operation,
-    delay: U256,
-    explorer_url: bool,
-    cfg: ZKSyncConfig,
-) -> eyre::Result<()> {
-    Command::ProposeUpgrade {
-        shadow: false,
-        operation_id: None,
-        delay,
-        transparent: true,
-        operation: Some(operation),
-        explorer_url,
+    #[test]
+    fn hash_operation_matches_governance() -> eyre::Result<()> {
+        // keccak256(abi.encode(operation)), as computed by Governance.hashOperation.
+        assert_eq!(
+            hash_operation(&operation()?),
+            H256::from_str("0xfcd262a0bb4fd3c8729495957c60b03b12bd5501f6dd528e9af9763d867f52f3")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_operation_round_trip() -> eyre::Result<()> {
+        let operation = operation()?;
+        let parsed_operation = parse_operation(&operation_to_json(&operation).to_string())?;
+        assert_eq!(parsed_operation.calls.len(), operation.calls.len());
+        for (parsed_call, call) in parsed_operation.calls.iter().zip(&operation.calls) {
+            assert_eq!(parsed_call.target, call.target);
+            assert_eq!(parsed_call.value, call.value);
+            assert_eq!(parsed_call.data, call.data);
+        }
+        assert_eq!(parsed_operation.predecessor, operation.predecessor);
+        assert_eq!(parsed_operation.salt, operation.salt);
+        assert_eq!(
+            hash_operation(&parsed_operation),
+            hash_operation(&operation)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_operation_reads_files_given_with_at() -> eyre::Result<()> {
+        let operation = operation()?;
+        let path = std::env::temp_dir().join(format!("zks-operation-{}.json", std::process::id()));
+        std::fs::write(&path, operation_to_json(&operation).to_string())?;
+        let parsed_operation = parse_operation(&format!("@{}", path.display()));
+        std::fs::remove_file(&path)?;
+        assert_eq!(
+            hash_operation(&parsed_operation?),
+            hash_operation(&operation)
+        );
+
+        match parse_operation("calls.json") {
+            Ok(_) => eyre::bail!("A path without @ is not an operation"),
+            Err(e) => assert_eq!(e.to_string(), "Invalid JSON"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse_operation_hex_and_default_values() -> eyre::Result<()> {
+        let parsed_operation = parse_operation(
+            r#"{"calls": [{"target": "0x1111111111111111111111111111111111111111", "value": "0xde0b6b3a7640000", "data": "0x"}]}"#,
+        )?;
+        let call = parsed_operation
+            .calls
+            .first()
+            .ok_or_else(|| eyre::eyre!("No call"))?;
+        assert_eq!(call.value, U256::exp10(18_usize));
+        assert_eq!(parsed_operation.predecessor, [0_u8; 32]);
+        assert_eq!(parsed_operation.salt, [0_u8; 32]);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_call_with_signature() -> eyre::Result<()> {
+        let call = parse_call(
+            "0x1111111111111111111111111111111111111111:0x10 transfer(address,uint256) 0x2222222222222222222222222222222222222222 5",
+        )?;
+        let mut data = get_fn_selector("transfer(address,uint256)").to_vec();
+        data.extend(encode(&[
+            Token::Address(Address::from_str(
+                "0x2222222222222222222222222222222222222222",
+            )?),
+            Token::Uint(U256::from(5_u64)),
+        ]));
+        assert_eq!(
+            call.target,
+            Address::from_str("0x1111111111111111111111111111111111111111")?
+        );
+        assert_eq!(call.value, U256::from(16_u64));
+        assert_eq!(call.data, Bytes::from(data));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_call_arguments_with_spaces() -> eyre::Result<()> {
+        let call = parse_call(
+            r#"0x1111111111111111111111111111111111111111 set(string,uint256[],(address,string)) "hello world" [1, 2] (0x2222222222222222222222222222222222222222, "a b")"#,
+        )?;
+        let mut data = get_fn_selector("set(string,uint256[],(address,string))").to_vec();
+        data.extend(encode(&[
+            Token::String("hello world".to_owned()),
+            Token::Array(vec![
+                Token::Uint(U256::from(1_u64)),
+                Token::Uint(U256::from(2_u64)),
+            ]),
+            Token::Tuple(vec![
+                Token::Address(Address::from_str(
+                    "0x2222222222222222222222222222222222222222",
+                )?),
+                Token::String("a b".to_owned()),
+            ]),
+        ]));
+        assert_eq!(call.data, Bytes::from(data));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_call_with_calldata() -> eyre::Result<()> {
+        let call = parse_call("0x1111111111111111111111111111111111111111 0x12345678")?;
+        assert_eq!(call.value, U256::zero());
+        assert_eq!(call.data, Bytes::from_str("0x12345678")?);
+        assert!(parse_call("0x1111111111111111111111111111111111111111 0x12345678 1").is_err());
+        assert!(parse_call(r#"0x1111111111111111111111111111111111111111 f(string) "a"#).is_err());
+        Ok(())
     }
-    .run(cfg)
-    .await
 }
@@ -4,6 +4,7 @@ use crate::config::ZKSyncConfig;
 
 pub(crate) mod bridgehub;
 pub(crate) mod governance;
+pub(crate) mod upgrade;
 
 #[derive(Subcommand)]
 pub(crate) enum Command {
@@ -37,6 +38,12 @@ pub(crate) enum Command {
         visible_alias = "stm"
     )]
     StateTransitionManager,
+    #[clap(
+        subcommand,
+        about = "Hyperchain diamond cut upgrade commands.",
+        visible_alias = "u"
+    )]
+    Upgrade(upgrade::Command),
 }
 
 impl Command {
@@ -47,6 +54,7 @@ impl Command {
             Command::Hyperchain => todo!(),
             Command::L1SharedBridge => todo!(),
             Command::StateTransitionManager => todo!(),
+            Command::Upgrade(cmd) => cmd.run()?,
         };
 
         Ok(())
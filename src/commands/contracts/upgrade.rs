@@ -0,0 +1,164 @@
+use crate::{
+    commands::contracts::governance::{parse_u256, write_operation},
+    utils::{
+        contract::get_fn_selector,
+        upgrade::{DiamondCutData, FacetAction, FacetCut},
+    },
+};
+use clap::Subcommand;
+use eyre::ContextCompat;
+use std::{path::PathBuf, str::FromStr};
+use zksync_ethers_rs::{
+    contracts::governance::{Call, Operation},
+    types::{Address, Bytes, H256, U256},
+};
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    #[clap(
+        about = "Build the diamond cut upgrade calldata of a hyperchain and the governance operation calling it."
+    )]
+    Build {
+        #[clap(
+            long,
+            required_unless_present = "stm",
+            conflicts_with = "stm",
+            requires = "from_protocol_version",
+            help = "Diamond proxy of the hyperchain to upgrade with upgradeChainFromVersion"
+        )]
+        hyperchain: Option<Address>,
+        #[clap(
+            long,
+            requires = "chain_id",
+            help = "StateTransitionManager executing the upgrade of the hyperchain of --chain-id"
+        )]
+        stm: Option<Address>,
+        #[clap(long, requires = "stm", value_parser = parse_u256)]
+        chain_id: Option<U256>,
+        #[clap(
+            long = "facet",
+            value_name = "FACET_CUT",
+            value_parser = parse_facet_cut,
+            help = "\"FACET ACTION[:freezable] SELECTOR...\", ACTION being add, replace or remove and the selectors 4 bytes hex or function signatures, e.g. \"0x1234... add getName() 0xcdffacc6\""
+        )]
+        facet_cuts: Vec<FacetCut>,
+        #[clap(
+            long,
+            required = false,
+            help = "Contract delegate-called after the facet cuts, e.g. the upgrade contract"
+        )]
+        init_address: Option<Address>,
+        #[clap(long, requires = "init_address", required = false)]
+        init_calldata: Option<Bytes>,
+        #[clap(
+            long,
+            value_parser = parse_u256,
+            requires = "hyperchain",
+            required = false,
+            help = "Protocol version being upgraded by the hyperchain upgradeChainFromVersion"
+        )]
+        from_protocol_version: Option<U256>,
+        #[clap(long, required = false)]
+        predecessor: Option<H256>,
+        #[clap(long, required = false)]
+        salt: Option<H256>,
+        #[clap(
+            long,
+            short = 'o',
+            value_name = "FILE",
+            required = false,
+            help = "Write the governance operation to FILE"
+        )]
+        out: Option<PathBuf>,
+    },
+}
+
+impl Command {
+    pub fn run(self) -> eyre::Result<()> {
+        match self {
+            Command::Build {
+                hyperchain,
+                stm,
+                chain_id,
+                facet_cuts,
+                init_address,
+                init_calldata,
+                from_protocol_version,
+                predecessor,
+                salt,
+                out,
+            } => {
+                if facet_cuts.is_empty() && init_address.is_none() {
+                    eyre::bail!("The upgrade has neither facet cuts nor an init address");
+                }
+                let diamond_cut = DiamondCutData {
+                    facet_cuts,
+                    init_address: init_address.unwrap_or_default(),
+                    init_calldata: init_calldata.unwrap_or_default(),
+                };
+                let (target, upgrade_calldata) =
+                    match (hyperchain, from_protocol_version, stm, chain_id) {
+                        (Some(hyperchain), Some(from_protocol_version), None, None) => (
+                            hyperchain,
+                            diamond_cut.upgrade_chain_from_version_calldata(from_protocol_version),
+                        ),
+                        (None, None, Some(stm), Some(chain_id)) => {
+                            (stm, diamond_cut.stm_execute_upgrade_calldata(chain_id))
+                        }
+                        _ => eyre::bail!(
+                            "Give either --hyperchain and --from-protocol-version, or --stm and --chain-id"
+                        ),
+                    };
+                println!("Upgrade calldata: {upgrade_calldata}");
+                let operation = Operation {
+                    calls: vec![Call {
+                        target,
+                        value: U256::zero(),
+                        data: upgrade_calldata,
+                    }],
+                    predecessor: predecessor.unwrap_or_default().0,
+                    salt: salt.unwrap_or_default().0,
+                };
+                write_operation(&operation, out.as_deref())?;
+            }
+        };
+        Ok(())
+    }
+}
+
+/// Parses a `FACET ACTION[:freezable] SELECTOR...` facet cut.
+fn parse_facet_cut(raw_facet_cut: &str) -> eyre::Result<FacetCut> {
+    let mut parts = raw_facet_cut.split_whitespace();
+    let facet: Address = parts.next().context("Empty facet cut")?.parse()?;
+    let raw_action = parts.next().context("No action in facet cut")?;
+    let (action, is_freezable) = match raw_action.split_once(':') {
+        Some((action, "freezable")) => (FacetAction::from_str(action)?, true),
+        Some((_, flag)) => eyre::bail!("Invalid facet cut flag {flag}, expected freezable"),
+        None => (FacetAction::from_str(raw_action)?, false),
+    };
+    let selectors = parts
+        .map(|selector| match selector.strip_prefix("0x") {
+            Some(hex_selector) => <[u8; 4]>::try_from(hex::decode(hex_selector)?.as_slice())
+                .map_err(|e| eyre::eyre!("Selector {selector} is not 4 bytes long: {e}")),
+            None => Ok(get_fn_selector(selector)),
+        })
+        .collect::<eyre::Result<Vec<[u8; 4]>>>()?;
+    if selectors.is_empty() {
+        eyre::bail!("Facet cut of {facet:?} has no selectors");
+    }
+    match action {
+        FacetAction::Remove if !facet.is_zero() => {
+            eyre::bail!("The removed selectors must have the zero address as facet")
+        }
+        FacetAction::Add | FacetAction::Replace if facet.is_zero() => {
+            eyre::bail!("The {action} facet cut must have a facet address")
+        }
+        FacetAction::Add | FacetAction::Replace | FacetAction::Remove => {}
+    }
+    Ok(FacetCut {
+        facet,
+        action,
+        is_freezable,
+        selectors,
+    })
+}
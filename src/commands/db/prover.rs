@@ -33,7 +33,11 @@ use crate::{
             DATABASE_PROVER_RESTART_ALREADY_PROVED_BATCH_PROOF_CONFIRMATION_MSG,
             DATABASE_PROVER_RESTART_BATCH_PROOF_CONFIRMATION_MSG,
         },
-        prover_status::{display_batch_info, display_batch_status, get_batches_data, Status},
+        prover_exporter::run_exporter,
+        prover_status::{
+            display_batch_circuits_status, display_batch_info, display_batch_status,
+            get_batches_data, Status,
+        },
     },
 };
 use chrono::{offset::TimeZone, DateTime, Local};
@@ -41,6 +45,7 @@ use clap::Subcommand;
 use colored::Colorize;
 use eyre::ContextCompat;
 use spinoff::{spinners::Dots, Color, Spinner};
+use std::{net::SocketAddr, time::Duration};
 use zksync_ethers_rs::types::{
     zksync::{
         basic_fri_types::AggregationRound, protocol_version::VersionPatch,
@@ -123,6 +128,14 @@ pub(crate) enum Command {
         )]
         compressor: bool,
     },
+    #[clap(
+        about = "Display the status, attempts and time taken of each circuit's prover jobs by aggregation round for a given sequence of L1BatchNumbers.",
+        visible_alias = "circuits"
+    )]
+    CircuitStatus {
+        #[clap(short = 'n', num_args = 1.., required = true)]
+        batches: Vec<L1BatchNumber>,
+    },
     #[clap(
         about = "Calculates the ProofTime of batches generated from now up to a specified number of days in the past."
     )]
@@ -140,6 +153,19 @@ pub(crate) enum Command {
         )]
         days: u32,
     },
+    #[clap(
+        about = "Periodically collect prover DB health metrics (stuck jobs, queue depth, proof times) and serve them on a Prometheus /metrics endpoint."
+    )]
+    Exporter {
+        #[clap(long, default_value = "0.0.0.0:9100")]
+        listen: SocketAddr,
+        #[clap(
+            long,
+            default_value_t = 30,
+            help = "Seconds between collections from the prover DB"
+        )]
+        interval: u64,
+    },
 }
 
 #[allow(unused, reason = "not used atm")]
@@ -162,51 +188,76 @@ impl Command {
                 find_map_stuck_wg_jobs_in_aggregation_round(
                     aggregation_round,
                     map_bwg_info,
+                    &cfg.stuck_jobs,
+                    &mut prover_db,
+                )
+                .await?;
+                find_stuck_prover_jobs_in_aggregation_round(
+                    aggregation_round,
+                    &cfg.stuck_jobs,
                     &mut prover_db,
                 )
                 .await?;
-                find_stuck_prover_jobs_in_aggregation_round(aggregation_round, &mut prover_db)
-                    .await?;
 
                 aggregation_round = AggregationRound::LeafAggregation;
                 find_map_stuck_wg_jobs_in_aggregation_round(
                     aggregation_round,
                     map_leaf_wg_info,
+                    &cfg.stuck_jobs,
+                    &mut prover_db,
+                )
+                .await?;
+                find_stuck_prover_jobs_in_aggregation_round(
+                    aggregation_round,
+                    &cfg.stuck_jobs,
                     &mut prover_db,
                 )
                 .await?;
-                find_stuck_prover_jobs_in_aggregation_round(aggregation_round, &mut prover_db)
-                    .await?;
 
                 aggregation_round = AggregationRound::NodeAggregation;
                 find_map_stuck_wg_jobs_in_aggregation_round(
                     aggregation_round,
                     map_node_wg_info,
+                    &cfg.stuck_jobs,
+                    &mut prover_db,
+                )
+                .await?;
+                find_stuck_prover_jobs_in_aggregation_round(
+                    aggregation_round,
+                    &cfg.stuck_jobs,
                     &mut prover_db,
                 )
                 .await?;
-                find_stuck_prover_jobs_in_aggregation_round(aggregation_round, &mut prover_db)
-                    .await?;
 
                 aggregation_round = AggregationRound::RecursionTip;
                 find_map_stuck_wg_jobs_in_aggregation_round(
                     aggregation_round,
                     map_recursion_tip_wg_info,
+                    &cfg.stuck_jobs,
+                    &mut prover_db,
+                )
+                .await?;
+                find_stuck_prover_jobs_in_aggregation_round(
+                    aggregation_round,
+                    &cfg.stuck_jobs,
                     &mut prover_db,
                 )
                 .await?;
-                find_stuck_prover_jobs_in_aggregation_round(aggregation_round, &mut prover_db)
-                    .await?;
 
                 aggregation_round = AggregationRound::Scheduler;
                 find_map_stuck_wg_jobs_in_aggregation_round(
                     aggregation_round,
                     map_scheduler_wg_info,
+                    &cfg.stuck_jobs,
+                    &mut prover_db,
+                )
+                .await?;
+                find_stuck_prover_jobs_in_aggregation_round(
+                    aggregation_round,
+                    &cfg.stuck_jobs,
                     &mut prover_db,
                 )
                 .await?;
-                find_stuck_prover_jobs_in_aggregation_round(aggregation_round, &mut prover_db)
-                    .await?;
             }
             Command::RestartBatchProof { batch_number } => {
                 let mut spinner = Spinner::new(Dots, "Checking batch proof status", Color::Blue);
@@ -392,8 +443,9 @@ impl Command {
                         "=".repeat(8)
                     );
 
-                    if let Status::Custom(msg) =
-                        batch_data.compressor.witness_generator_jobs_status(10)
+                    if let Status::Custom(msg) = batch_data
+                        .compressor
+                        .witness_generator_jobs_status(&cfg.stuck_jobs)
                     {
                         if msg.contains("Sent to server") {
                             println!("> Proof sent to server ✅");
@@ -403,19 +455,36 @@ impl Command {
 
                     let basic_witness_generator_status = batch_data
                         .basic_witness_generator
-                        .witness_generator_jobs_status(10);
+                        .witness_generator_jobs_status(&cfg.stuck_jobs);
                     if matches!(basic_witness_generator_status, Status::JobsNotFound) {
                         println!("> No batch found. 🚫");
                         continue;
                     }
 
                     if !verbose {
-                        display_batch_status(batch_data, flags);
+                        display_batch_status(batch_data, flags, &cfg.stuck_jobs);
                     } else {
-                        display_batch_info(batch_data, flags)?;
+                        display_batch_info(batch_data, flags, &cfg.stuck_jobs)?;
                     }
                 }
             }
+            Command::Exporter { listen, interval } => {
+                run_exporter(
+                    listen,
+                    Duration::from_secs(interval),
+                    cfg.db.context("DB config missing")?.prover,
+                    cfg.stuck_jobs,
+                )
+                .await?;
+            }
+            Command::CircuitStatus { batches } => {
+                let mut spinner = Spinner::new(Dots, "Fetching Batches", Color::Blue);
+                let batches_data = get_batches_data(batches, &mut prover_db).await?;
+                spinner.success("Data Retrieved from DB");
+                for batch_data in batches_data {
+                    display_batch_circuits_status(batch_data, &cfg.stuck_jobs);
+                }
+            }
             Command::ProofTime { batch, days } => {
                 let mut spinner = Spinner::new(Dots, "Fetching Data", Color::Blue);
 
@@ -0,0 +1,147 @@
+use crate::{
+    config::ZKSyncConfig,
+    utils::{
+        db::for_batch_queries::{
+            get_proof_basic_witness_generator_info_for_batch, get_prover_jobs_info_for_batch,
+        },
+        prover_artifacts::{blob_path, inspect_artifact, ArtifactKind},
+    },
+};
+use clap::Subcommand;
+use eyre::ContextCompat;
+use std::path::PathBuf;
+use zksync_ethers_rs::types::zksync::{basic_fri_types::AggregationRound, L1BatchNumber};
+
+// The default root of zksync-era's file backed object store.
+pub const DEFAULT_OBJECT_STORE_PATH: &str = "./artifacts";
+
+#[derive(Subcommand)]
+pub(crate) enum Command {
+    #[clap(
+        about = "Decode and display a circuit, proof or witness inputs blob. The blob can be given by path or resolved from the prover DB by batch and circuit."
+    )]
+    Inspect {
+        #[clap(required_unless_present = "batch", conflicts_with = "batch")]
+        path: Option<PathBuf>,
+        #[clap(short = 'n', long)]
+        batch: Option<L1BatchNumber>,
+        #[clap(
+            short = 'c',
+            long = "circuit",
+            requires = "batch",
+            help = "BaseLayerCircuitType numeric value of the prover job, not needed for the recursion tip and scheduler rounds"
+        )]
+        circuit_id: Option<u8>,
+        #[clap(
+            short = 'r',
+            long,
+            default_value_t = 0,
+            requires = "batch",
+            help = "Aggregation round of the prover job"
+        )]
+        round: u8,
+        #[clap(
+            long,
+            requires = "batch",
+            help = "Sequence number of the prover job, all jobs are inspected if not set"
+        )]
+        sequence: Option<u32>,
+        #[clap(long, value_enum, help = "Inferred from the blob's bucket if not set")]
+        kind: Option<ArtifactKind>,
+        #[clap(long, default_value = DEFAULT_OBJECT_STORE_PATH)]
+        object_store: PathBuf,
+    },
+}
+
+impl Command {
+    pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
+        match self {
+            Command::Inspect {
+                path,
+                batch,
+                circuit_id,
+                round,
+                sequence,
+                kind,
+                object_store,
+            } => {
+                if let Some(path) = path {
+                    return inspect_artifact(&path, kind);
+                }
+                let batch = batch.context("--batch is required if no path is given")?;
+                let mut prover_db = cfg
+                    .db
+                    .context("DB config missing")?
+                    .prover
+                    .acquire()
+                    .await?;
+                if matches!(kind, Some(ArtifactKind::WitnessInputs)) {
+                    let witness_inputs_blob_url =
+                        get_proof_basic_witness_generator_info_for_batch(batch, &mut prover_db)
+                            .await?
+                            .and_then(|job| job._witness_inputs_blob_url)
+                            .context("No witness inputs found for batch")?;
+                    return inspect_artifact(
+                        &blob_path(
+                            &object_store,
+                            ArtifactKind::WitnessInputs,
+                            &witness_inputs_blob_url,
+                        ),
+                        kind,
+                    );
+                }
+                let round = AggregationRound::from(round);
+                // Recursion tip and scheduler jobs don't belong to a base layer circuit.
+                let circuit_id = if matches!(
+                    round,
+                    AggregationRound::RecursionTip | AggregationRound::Scheduler
+                ) {
+                    None
+                } else {
+                    Some(circuit_id.context(
+                        "--circuit is required with --batch for the basic, leaf and node rounds",
+                    )?)
+                };
+                let prover_jobs = get_prover_jobs_info_for_batch(batch, round, &mut prover_db)
+                    .await?
+                    .into_iter()
+                    .filter(|job| {
+                        circuit_id.map_or(true, |id| job.base_layer_circuit_id() == Some(id))
+                    })
+                    .filter(|job| sequence.map_or(true, |s| job._sequence_number == s))
+                    .collect::<Vec<_>>();
+                if prover_jobs.is_empty() {
+                    match circuit_id {
+                        Some(circuit_id) => eyre::bail!(
+                            "No prover jobs found for batch {batch} and circuit {circuit_id}"
+                        ),
+                        None => eyre::bail!("No prover jobs found for batch {batch}"),
+                    }
+                }
+                for job in prover_jobs {
+                    if !matches!(kind, Some(ArtifactKind::Proof)) {
+                        inspect_artifact(
+                            &blob_path(
+                                &object_store,
+                                ArtifactKind::Circuit,
+                                &job._circuit_blob_url,
+                            ),
+                            Some(ArtifactKind::Circuit),
+                        )?;
+                    }
+                    if matches!(kind, Some(ArtifactKind::Circuit)) {
+                        continue;
+                    }
+                    match job._proof_blob_url {
+                        Some(proof_blob_url) => inspect_artifact(
+                            &blob_path(&object_store, ArtifactKind::Proof, &proof_blob_url),
+                            Some(ArtifactKind::Proof),
+                        )?,
+                        None => println!("Prover job {} has no proof yet", job._id),
+                    }
+                }
+            }
+        };
+        Ok(())
+    }
+}
@@ -3,8 +3,16 @@ use clap::Subcommand;
 use std::path::PathBuf;
 use zksync_ethers_rs::types::zksync::inputs::WitnessInputData;
 
+pub(crate) mod artifact;
+
 #[derive(Subcommand)]
 pub(crate) enum Command {
+    #[clap(
+        subcommand,
+        about = "Prover object store artifacts commands.",
+        visible_alias = "a"
+    )]
+    Artifact(artifact::Command),
     #[clap(
         about = "Prover - Debug Witness Inputs",
         visible_alias = "debug-proof-gen-data"
@@ -23,8 +31,9 @@ pub(crate) enum Command {
 }
 
 impl Command {
-    pub async fn run(self, _cfg: ZKSyncConfig) -> eyre::Result<()> {
+    pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
         match self {
+            Command::Artifact(cmd) => cmd.run(cfg).await?,
             Command::DebugWitnessInputs {
                 file_path,
                 vm_run_data,
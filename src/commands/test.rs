@@ -1,23 +1,53 @@
 use crate::config::ZKSyncConfig;
 use crate::utils::{
-    balance::display_balance, balance::get_erc20_decimals_symbol, gas_tracker::GasTracker, test::*,
+    balance::display_balance,
+    balance::get_erc20_decimals_symbol,
+    gas_tracker::GasTracker,
+    mock_signer::serve_mock_signer,
+    test::*,
+    test_runs::{load_test_run, remove_test_run, save_test_run},
+    try_l1_provider_from_config, try_l2_provider_from_config,
     wallet::*,
 };
-use clap::Subcommand;
+use clap::{Parser, Subcommand};
 use colored::*;
 use core::time;
 use eyre::ContextCompat;
 use spinoff::{spinners, Color, Spinner};
 use std::{
+    net::SocketAddr,
     ops::{Add, Div},
     sync::Arc,
     thread::sleep,
 };
 use zksync_ethers_rs::{
-    core::utils::parse_units, providers::Middleware, types::Address, types::U256,
-    wait_for_finalize_withdrawal, ZKMiddleware,
+    core::utils::parse_units,
+    providers::{Http, Middleware, Provider},
+    signers::LocalWallet,
+    types::Address,
+    types::U256,
+    wait_for_finalize_withdrawal,
+    zk_wallet::ZKWallet,
+    ZKMiddleware,
 };
 
+/// Test wallets derivation, random wallets are generated if no mnemonic is given.
+#[derive(Parser)]
+pub(crate) struct DerivationOpts {
+    #[arg(
+        long = "mnemonic",
+        help = "Derive the test wallets from this mnemonic instead of generating random ones, so they can be recovered later"
+    )]
+    mnemonic: Option<String>,
+    #[arg(
+        long = "derivation-start",
+        default_value_t = 0,
+        requires = "mnemonic",
+        help = "Derivation index of the first test wallet"
+    )]
+    derivation_start: u32,
+}
+
 #[derive(Subcommand)]
 pub(crate) enum Command {
     #[clap(about = "LoadTest the zkStack Chain.", visible_alias = "lt")]
@@ -50,6 +80,8 @@ pub(crate) enum Command {
             help = "Sleep interval between each rerun"
         )]
         sleep_secs: u64,
+        #[command(flatten)]
+        derivation_opts: DerivationOpts,
     },
     #[clap(
         about = "Gas Measurements for the zkStack Chain.",
@@ -72,6 +104,8 @@ pub(crate) enum Command {
             help = "Amount of times to run the program in a loop, it defaults to 1. Max is 255"
         )]
         reruns_wanted: u8,
+        #[command(flatten)]
+        derivation_opts: DerivationOpts,
     },
     #[clap(
         about = "LoadTest with contract interactions for the zkStack Chain.\nCustom command, the contract performs a fibonacci calculation and stores the value.",
@@ -95,11 +129,39 @@ pub(crate) enum Command {
         )]
         reruns_wanted: u8,
     },
+    #[clap(
+        about = "Send the L2 balances of a test run wallets back to the rich wallet.\nThe run id is printed when the test wallets are created."
+    )]
+    Sweep {
+        #[arg(help = "Test run id")]
+        run_id: String,
+    },
+    #[clap(
+        about = "Serve a mock remote signer holding the given key, to test remote signer wallets."
+    )]
+    MockSigner {
+        #[arg(long, help = "Private key held by the mock signer")]
+        private_key: LocalWallet,
+        #[arg(long, default_value = "127.0.0.1:8550")]
+        listen: SocketAddr,
+    },
 }
 
 impl Command {
     pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
-        let (zk_wallet, l1_provider, l2_provider) = get_wallet_l1_l2_providers(cfg)?;
+        if let Command::MockSigner {
+            private_key,
+            listen,
+        } = self
+        {
+            return serve_mock_signer(private_key, listen).await;
+        }
+
+        let l1_provider = try_l1_provider_from_config(&cfg)?;
+        let l2_provider = try_l2_provider_from_config(&cfg)?;
+        // Test runs sign lots of transactions, so they need the wallet's local key.
+        let zk_wallet =
+            new_zkwallet(cfg.wallet_config()?.signer()?, &l1_provider, &l2_provider).await?;
         let base_token_address = l2_provider.get_base_token_l1_address().await?;
         let (base_token_decimals, base_token_symbol) =
             get_erc20_decimals_symbol(base_token_address, &l1_provider).await?;
@@ -116,9 +178,18 @@ impl Command {
                 reruns_wanted,
                 withdraw,
                 sleep_secs,
+                derivation_opts,
             } => {
-                let wallets =
-                    get_n_random_wallets(number_of_wallets, &l1_provider, &l2_provider).await?;
+                let wallets = get_n_wallets(
+                    number_of_wallets,
+                    derivation_opts.mnemonic.as_deref(),
+                    derivation_opts.derivation_start,
+                    &l1_provider,
+                    &l2_provider,
+                )
+                .await?;
+                let run_id = save_run(&wallets)?;
+                let mut transfers_back = 0;
                 // ideally it should be the amount transferred, the gas + fees have to be deducted automatically
                 let parsed_amount_to_deposit: U256 =
                     parse_units(amount, base_token_decimals)?.into();
@@ -186,7 +257,9 @@ impl Command {
 
                     display_balance(None, &arc_zk_wallet, false, true).await?;
 
-                    send_transactions_back(&wallets, &arc_zk_wallet).await?;
+                    transfers_back = send_transactions_back(&wallets, &arc_zk_wallet)
+                        .await?
+                        .len();
 
                     display_balance(None, &arc_zk_wallet, false, true).await?;
 
@@ -230,12 +303,13 @@ impl Command {
                     sleep(time::Duration::from_secs(sleep_secs));
                     spinner.success(&format!("Rerun {current_reruns} finished"));
                 }
-                Ok(())
+                remove_finished_run(&run_id, wallets.len(), transfers_back)
             }
             Command::GasScenario {
                 tpr,
                 amount,
                 reruns_wanted,
+                derivation_opts,
             } => {
                 // Calculations are performed with the following conditions
                 // - Don't take deposits into account
@@ -257,8 +331,16 @@ impl Command {
                 let mut gas_ra_per_run;
                 let mut gas_ra_price_per_run;
 
-                let wallets =
-                    get_n_random_wallets(number_of_wallets, &l1_provider, &l2_provider).await?;
+                let wallets = get_n_wallets(
+                    number_of_wallets,
+                    derivation_opts.mnemonic.as_deref(),
+                    derivation_opts.derivation_start,
+                    &l1_provider,
+                    &l2_provider,
+                )
+                .await?;
+                let run_id = save_run(&wallets)?;
+                let mut transfers_back = 0;
                 // ideally it should be the amount transferred, the gas + fees have to be deducted automatically
                 let parsed_amount_to_deposit: U256 =
                     parse_units(amount, base_token_decimals)?.into();
@@ -309,6 +391,7 @@ impl Command {
 
                     let tx_hashes_backwards =
                         send_transactions_back(&wallets, &arc_zk_wallet).await?;
+                    transfers_back = tx_hashes_backwards.len();
 
                     // End Transfer from each wallet to rich wallet
                     println!("{}", "#".repeat(64));
@@ -363,7 +446,7 @@ impl Command {
                     sleep(time::Duration::from_millis(300));
                 }
                 println!("{gas_tracker}");
-                Ok(())
+                remove_finished_run(&run_id, wallets.len(), transfers_back)
             }
             Command::ContractInteraction {
                 tpr,
@@ -403,6 +486,55 @@ impl Command {
                 }
                 Ok(())
             }
+            Command::Sweep { run_id } => {
+                let signers = load_test_run(&run_id)?.signers()?;
+                let number_of_wallets = signers.len();
+                let wallets = zkwallets_from_signers(signers, &l1_provider, &l2_provider).await?;
+
+                display_balance(None, &arc_zk_wallet, false, true).await?;
+                let (tx_hashes, failed_wallets) = sweep_wallets(&wallets, &arc_zk_wallet).await?;
+                println!("{} transfers sent", tx_hashes.len());
+                display_balance(None, &arc_zk_wallet, false, true).await?;
+                if !failed_wallets.is_empty() {
+                    eyre::bail!(
+                        "{} of {number_of_wallets} wallets could not be swept, their keys are kept in test run {run_id}",
+                        failed_wallets.len()
+                    );
+                }
+                remove_test_run(&run_id)?;
+                println!("Test run {run_id} swept, its keys were deleted");
+                Ok(())
+            }
+            Command::MockSigner { .. } => unreachable!("handled before loading the wallet"),
         }
     }
 }
+
+/// Saves the keys of the test wallets, so their funds can be swept if the run is interrupted.
+fn save_run(wallets: &[Arc<ZKWallet<Provider<Http>, LocalWallet>>]) -> eyre::Result<String> {
+    let run_id = save_test_run(wallets)?;
+    println!(
+        "Test run {} saved, if interrupted recover its funds with `zks test sweep {run_id}`",
+        run_id.bold()
+    );
+    Ok(run_id)
+}
+
+/// Deletes the keys of a finished test run once every wallet sent its funds back, they are
+/// kept to sweep the run otherwise.
+fn remove_finished_run(
+    run_id: &str,
+    number_of_wallets: usize,
+    transfers_back: usize,
+) -> eyre::Result<()> {
+    if transfers_back < number_of_wallets {
+        println!(
+            "{} of {number_of_wallets} wallets did not send their funds back, recover them with `zks test sweep {run_id}`",
+            number_of_wallets.saturating_sub(transfers_back)
+        );
+        return Ok(());
+    }
+    remove_test_run(run_id)?;
+    println!("Test run {run_id} finished, its keys were deleted");
+    Ok(())
+}
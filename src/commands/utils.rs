@@ -26,16 +26,7 @@ impl Command {
     pub fn run(self, _cfg: ZKSyncConfig) -> eyre::Result<()> {
         match self {
             Command::CalldataEncode { signature, args } => {
-                let abi = parse_abi_str(signature.as_str())?;
-                let function = abi.functions().next().context("No functions found")?;
-                let args = function
-                    .inputs
-                    .clone()
-                    .into_iter()
-                    .zip(args.iter())
-                    .map(|(param, raw_param)| parse_param_into_token(param.kind, raw_param))
-                    .collect::<eyre::Result<Vec<Token>>>()?;
-                let encoded = function.encode_input(&args)?;
+                let encoded = encode_calldata(&signature, &args)?;
                 println!("0x{}", hex::encode(encoded));
             }
             Command::CalldataDecode {
@@ -58,6 +49,29 @@ impl Command {
     }
 }
 
+/// Encodes a call to the function with the given signature, e.g. `transfer(address,uint256)`,
+/// parsing its arguments from their string representation.
+pub(crate) fn encode_calldata(signature: &str, args: &[String]) -> eyre::Result<Vec<u8>> {
+    let abi = parse_abi_str(signature)?;
+    let function = abi.functions().next().context("No functions found")?;
+    if args.len() != function.inputs.len() {
+        eyre::bail!(
+            "{} takes {} arguments, {} given",
+            function.name,
+            function.inputs.len(),
+            args.len()
+        );
+    }
+    let args = function
+        .inputs
+        .clone()
+        .into_iter()
+        .zip(args.iter())
+        .map(|(param, raw_param)| parse_param_into_token(param.kind, raw_param))
+        .collect::<eyre::Result<Vec<Token>>>()?;
+    Ok(function.encode_input(&args)?)
+}
+
 fn parse_param_into_token(param_kind: ParamType, raw_param: &str) -> eyre::Result<Token> {
     match param_kind {
         zksync_ethers_rs::abi::ParamType::Address => raw_param
@@ -126,21 +140,97 @@ fn parse_param_into_token(param_kind: ParamType, raw_param: &str) -> eyre::Resul
             .map(Tokenizable::into_token)
             .map_err(Into::<eyre::Error>::into),
         zksync_ethers_rs::abi::ParamType::String => Ok(Token::String(raw_param.to_owned())),
-        zksync_ethers_rs::abi::ParamType::Array(_param_type) => todo!(),
-        zksync_ethers_rs::abi::ParamType::FixedBytes(_size) => todo!(),
-        zksync_ethers_rs::abi::ParamType::FixedArray(_param_type, _size) => {
-            todo!()
+        zksync_ethers_rs::abi::ParamType::Array(param_type) => {
+            let items = split_list(raw_param, '[', ']')?
+                .into_iter()
+                .map(|item| parse_param_into_token((*param_type).clone(), &item))
+                .collect::<eyre::Result<Vec<Token>>>()?;
+            Ok(Token::Array(items))
+        }
+        zksync_ethers_rs::abi::ParamType::FixedBytes(size) => {
+            let bytes = raw_param.parse::<Bytes>()?;
+            if bytes.len() != size {
+                eyre::bail!("{raw_param} is not {size} bytes long");
+            }
+            Ok(Token::FixedBytes(bytes.to_vec()))
+        }
+        zksync_ethers_rs::abi::ParamType::FixedArray(param_type, size) => {
+            let items = split_list(raw_param, '[', ']')?;
+            if items.len() != size {
+                eyre::bail!("{raw_param} does not have {size} items");
+            }
+            let items = items
+                .into_iter()
+                .map(|item| parse_param_into_token((*param_type).clone(), &item))
+                .collect::<eyre::Result<Vec<Token>>>()?;
+            Ok(Token::FixedArray(items))
         }
         zksync_ethers_rs::abi::ParamType::Tuple(param_types) => {
+            let items = split_list(raw_param, '(', ')')?;
+            if items.len() != param_types.len() {
+                eyre::bail!("{raw_param} does not have {} items", param_types.len());
+            }
             let parsed_params = param_types
                 .into_iter()
-                .map(|param_type| parse_param_into_token(param_type, raw_param))
+                .zip(items)
+                .map(|(param_type, item)| parse_param_into_token(param_type, &item))
                 .collect::<eyre::Result<Vec<Token>>>()?;
             Ok(Token::Tuple(parsed_params))
         }
     }
 }
 
+/// Splits an `[a, b]` array or `(a, b)` tuple into its items, on the commas outside of double
+/// quotes and nested arrays or tuples. The items are trimmed and their quotes removed.
+fn split_list(raw_list: &str, open: char, close: char) -> eyre::Result<Vec<String>> {
+    let inner = raw_list
+        .trim()
+        .strip_prefix(open)
+        .and_then(|raw_list| raw_list.strip_suffix(close))
+        .with_context(|| format!("{raw_list} is not enclosed in {open}{close}"))?;
+    if inner.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut items = Vec::new();
+    let mut item = String::new();
+    let mut depth = 0_usize;
+    let mut quoted = false;
+    for c in inner.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                item.push(c);
+            }
+            '[' | '(' if !quoted => {
+                depth += 1;
+                item.push(c);
+            }
+            ']' | ')' if !quoted => {
+                depth = depth
+                    .checked_sub(1)
+                    .with_context(|| format!("Unbalanced {c} in {raw_list}"))?;
+                item.push(c);
+            }
+            ',' if !quoted && depth == 0 => items.push(std::mem::take(&mut item)),
+            c => item.push(c),
+        }
+    }
+    if quoted || depth > 0 {
+        eyre::bail!("Unclosed quote or bracket in {raw_list}");
+    }
+    items.push(item);
+    Ok(items
+        .into_iter()
+        .map(|item| {
+            let item = item.trim();
+            item.strip_prefix('"')
+                .and_then(|item| item.strip_suffix('"'))
+                .unwrap_or(item)
+                .to_owned()
+        })
+        .collect())
+}
+
 fn display_token(token: Token) -> eyre::Result<()> {
     match token {
         Token::Address(_) => println!("{:?}", token.into_address().context("Address")?),
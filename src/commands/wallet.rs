@@ -1,13 +1,40 @@
 use crate::config::ZKSyncConfig;
-use crate::utils::balance::display_balance;
-use crate::utils::wallet::get_wallet_l1_l2_providers;
+use crate::utils::amount::{confirm_amount, parse_amount};
+use crate::utils::balance::{
+    display_balance, display_l1_balance, get_base_token_decimals_symbol, get_erc20_decimals_symbol,
+    get_token_decimals_symbol, print_l2_base_token_balance,
+};
+use crate::utils::config::confirm;
+use crate::utils::deposit::{
+    display_l2_deposit_result, wait_for_l2_deposit, DEFAULT_DEPOSIT_TIMEOUT_SECS,
+};
+use crate::utils::keystore::{
+    decrypt_keystore, import_keystore, keystore_path, keystores, new_keystore,
+};
+use crate::utils::messages::{PRIVATE_KEY_PROMPT_MSG, WALLET_EXPORT_PRIVATE_KEY_CONFIRMATION_MSG};
+use crate::utils::paymaster::{send_l2_transaction_with_paymaster, PaymasterOpts};
+use crate::utils::signed_tx::{sign_l1_transaction, sign_l2_transaction, SignOpts};
+use crate::utils::tx::{estimate_l1_fee, estimate_l2_fee, TxOpts};
+use crate::utils::wallet::{
+    get_wallet_l1_l2_providers, is_l1_deposit_call_supported, l1_deposit_call, l1_transfer,
+    l2_withdraw_call, transfer_call,
+};
+use crate::utils::withdrawals::{
+    finalize_withdrawal_call, pending_withdrawals_of, remove_pending_withdrawal,
+    save_pending_withdrawal, withdrawal_status, PendingWithdrawal, WithdrawalStatus,
+};
+use crate::utils::{try_l1_provider_from_config, try_l2_provider_from_config};
 use clap::Subcommand;
+use dialoguer::{theme::ColorfulTheme, Password};
 use eyre::ContextCompat;
 use spinoff::{spinner, spinners, Color, Spinner};
+use std::{path::PathBuf, time::Duration};
 use zksync_ethers_rs::{
     abi::Hash,
-    core::utils::parse_ether,
-    types::{Address, U256},
+    core::utils::format_units,
+    providers::Middleware,
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip2718::TypedTransaction, Address, Eip1559TransactionRequest, U256},
     wait_for_finalize_withdrawal, ZKMiddleware,
 };
 
@@ -24,8 +51,17 @@ pub(crate) enum Command {
     },
     #[clap(about = "Deposit funds into some wallet.")]
     Deposit {
-        #[clap(long = "amount", value_parser = |f: &str| parse_ether(f))]
-        amount: U256,
+        #[clap(
+            long = "amount",
+            help = "Amount in token units, e.g. 1.5, or with an explicit unit, e.g. 1.5gwei"
+        )]
+        amount: String,
+        #[clap(
+            long,
+            required = false,
+            help = "Interpret the amount in the token smallest unit"
+        )]
+        raw: bool,
         #[clap(
             long = "token",
             help = "Specify the token address, the base token is used as default."
@@ -38,17 +74,63 @@ pub(crate) enum Command {
         to: Option<Address>,
         #[clap(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[clap(
+            long,
+            required = false,
+            help = "Wait for the deposit to be executed on L2"
+        )]
+        wait: bool,
+        #[clap(
+            long,
+            requires = "wait",
+            default_value_t = DEFAULT_DEPOSIT_TIMEOUT_SECS,
+            help = "Seconds to wait for the deposit to be executed on L2"
+        )]
+        timeout: u64,
+        #[command(flatten)]
+        tx_opts: TxOpts,
+    },
+    #[clap(about = "Follow an L1 deposit until it is executed on L2.")]
+    DepositStatus {
+        #[clap(help = "L1 deposit transaction hash")]
+        l1_deposit_tx_hash: Hash,
+        #[clap(
+            long,
+            default_value_t = DEFAULT_DEPOSIT_TIMEOUT_SECS,
+            help = "Seconds to wait for the deposit to be executed on L2"
+        )]
+        timeout: u64,
     },
     #[clap(about = "Finalize a pending withdrawal.")]
     FinalizeWithdraw {
-        #[clap(long = "hash")]
-        l2_withdrawal_tx_hash: Hash,
+        #[clap(long = "hash", required_unless_present = "all")]
+        l2_withdrawal_tx_hash: Option<Hash>,
+        #[clap(
+            long,
+            conflicts_with_all = ["l2_withdrawal_tx_hash", "sign_only"],
+            help = "Finalize every tracked withdrawal that is ready to be finalized"
+        )]
+        all: bool,
+        #[command(flatten)]
+        sign_opts: SignOpts,
     },
     #[clap(about = "Transfer funds to another wallet.")]
     Transfer {
-        #[clap(long = "amount", value_parser = |f: &str| parse_ether(f))]
-        amount: U256,
-        #[clap(long = "token")]
+        #[clap(
+            long = "amount",
+            help = "Amount in token units, e.g. 1.5, or with an explicit unit, e.g. 1.5gwei"
+        )]
+        amount: String,
+        #[clap(
+            long,
+            required = false,
+            help = "Interpret the amount in the token smallest unit"
+        )]
+        raw: bool,
+        #[clap(
+            long = "token",
+            help = "Specify the token address, the base token is used as default on L2 and ETH on L1."
+        )]
         token_address: Option<Address>,
         #[clap(long = "to")]
         to: Address,
@@ -60,11 +142,24 @@ pub(crate) enum Command {
         l1: bool,
         #[clap(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[command(flatten)]
+        paymaster: PaymasterOpts,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
-    #[clap(about = "Withdraw funds from the wallet. TODO.")]
+    #[clap(about = "Withdraw funds from the wallet.")]
     Withdraw {
-        #[clap(long = "amount", value_parser = |f: &str| parse_ether(f))]
-        amount: U256,
+        #[clap(
+            long = "amount",
+            help = "Amount in token units, e.g. 1.5, or with an explicit unit, e.g. 1.5gwei"
+        )]
+        amount: String,
+        #[clap(
+            long,
+            required = false,
+            help = "Interpret the amount in the token smallest unit"
+        )]
+        raw: bool,
         #[clap(
             long = "token",
             help = "Specify the token address, the base token is used as default."
@@ -72,21 +167,130 @@ pub(crate) enum Command {
         token_address: Option<Address>,
         #[clap(long, short = 'e', required = false)]
         explorer_url: bool,
+        #[clap(
+            long,
+            required = false,
+            help = "Wait for the withdrawal to be executed and finalize it"
+        )]
+        wait: bool,
+        #[command(flatten)]
+        paymaster: PaymasterOpts,
+        #[command(flatten)]
+        tx_opts: TxOpts,
     },
+    #[clap(about = "List the tracked withdrawals of the wallet with their status.")]
+    Withdrawals,
     #[clap(about = "Get the wallet address.")]
     Address,
     #[clap(about = "Get the wallet private key.")]
     PrivateKey,
+    #[clap(about = "Create a new wallet in an encrypted keystore.")]
+    New {
+        #[clap(help = "Keystore name")]
+        name: String,
+    },
+    #[clap(about = "Import a private key into an encrypted keystore.")]
+    Import {
+        #[clap(help = "Keystore name")]
+        name: String,
+        #[clap(long, help = "Prompted for if not set")]
+        private_key: Option<LocalWallet>,
+    },
+    #[clap(
+        about = "Export a keystore, either as the encrypted JSON file or as a plain private key."
+    )]
+    Export {
+        #[clap(help = "Keystore name")]
+        name: String,
+        #[clap(
+            long,
+            help = "Copy the encrypted keystore JSON to this path instead of printing the private key"
+        )]
+        output: Option<PathBuf>,
+    },
+    #[clap(about = "List the wallet keystores.")]
+    Keystores,
+    #[clap(about = "List the config wallets with their L1 and L2 balances.")]
+    List,
 }
 
 // TODO Handle ETH
 impl Command {
     pub async fn run(self, cfg: ZKSyncConfig) -> eyre::Result<()> {
-        let wallet_config = cfg
-            .clone()
-            .wallet
-            .clone()
-            .context("Wallet config missing")?;
+        // Keystore commands don't need a configured wallet.
+        match self {
+            Command::New { name } => {
+                let wallet = new_keystore(&name)?;
+                println!("Keystore {name} created");
+                println!("Wallet address: {:?}", wallet.address());
+                println!(
+                    "Use it with: zks config edit <config_name> --keystore {name} --address {:?}",
+                    wallet.address()
+                );
+                return Ok(());
+            }
+            Command::Import { name, private_key } => {
+                let wallet = match private_key {
+                    Some(wallet) => wallet,
+                    None => Password::with_theme(&ColorfulTheme::default())
+                        .with_prompt(PRIVATE_KEY_PROMPT_MSG)
+                        .interact()?
+                        .parse()?,
+                };
+                import_keystore(&name, &wallet)?;
+                println!("Keystore {name} imported");
+                println!("Wallet address: {:?}", wallet.address());
+                return Ok(());
+            }
+            Command::Export { name, output } => {
+                if let Some(output) = output {
+                    std::fs::copy(keystore_path(&name)?, &output)?;
+                    println!("Keystore {name} exported to {}", output.display());
+                } else if confirm(WALLET_EXPORT_PRIVATE_KEY_CONFIRMATION_MSG)? {
+                    let wallet = decrypt_keystore(&name)?;
+                    println!(
+                        "Wallet private key: 0x{}",
+                        hex::encode(wallet.signer().to_bytes())
+                    );
+                }
+                return Ok(());
+            }
+            Command::Keystores => {
+                for (name, address) in keystores()? {
+                    match address {
+                        Some(address) => println!("{name}: {address:?}"),
+                        None => println!("{name}"),
+                    }
+                }
+                return Ok(());
+            }
+            Command::List => {
+                let l1_provider = try_l1_provider_from_config(&cfg)?;
+                let l2_provider = try_l2_provider_from_config(&cfg)?;
+                let base_token_address = l2_provider.get_base_token_l1_address().await?;
+                let selected_wallet_address = cfg.wallet_config().ok().map(|w| w.address);
+                for (alias, wallet_config) in cfg.wallet_configs() {
+                    let selected = if Some(wallet_config.address) == selected_wallet_address {
+                        " (selected)"
+                    } else {
+                        ""
+                    };
+                    println!("{alias}{selected}: {:?}", wallet_config.address);
+                    display_l1_balance(wallet_config.address, None, &l1_provider).await?;
+                    print_l2_base_token_balance(
+                        base_token_address,
+                        wallet_config.address,
+                        &l2_provider,
+                        &l1_provider,
+                    )
+                    .await?;
+                }
+                return Ok(());
+            }
+            _ => (),
+        }
+
+        let wallet_config = cfg.wallet_config()?.clone();
 
         let l1_explorer_url = cfg
             .clone()
@@ -102,8 +306,7 @@ impl Command {
             .filter(|url| !url.is_empty())
             .unwrap_or("http://localhost:3010".to_owned());
 
-        let (zk_wallet, _l1_provider, l2_provider) = get_wallet_l1_l2_providers(cfg)?;
-        let base_token_address = l2_provider.get_base_token_l1_address().await?;
+        let (zk_wallet, l1_provider, l2_provider) = get_wallet_l1_l2_providers(cfg.clone())?;
 
         let send_frames = spinner!(["💸⮕⮕", " 💸⮕", "  💸"], 240);
         let recv_frames = spinner!(["  💸", " 💸⬅", "💸⬅⬅"], 240);
@@ -116,10 +319,111 @@ impl Command {
             } => display_balance(token_address, &zk_wallet, l1, l2).await?,
             Command::Deposit {
                 amount,
+                raw,
                 token_address,
                 to,
                 explorer_url,
+                wait,
+                timeout,
+                tx_opts,
             } => {
+                // Both build the deposit transaction themselves instead of the wallet.
+                if (tx_opts.estimate || tx_opts.sign_opts.sign_only.is_some())
+                    && !is_l1_deposit_call_supported(token_address, &l2_provider).await?
+                {
+                    eyre::bail!(
+                        "--estimate and --sign-only only support ETH deposits on chains using ETH as base token, deposit without them"
+                    );
+                }
+                let (decimals, symbol) = match token_address {
+                    Some(token_address) => {
+                        get_erc20_decimals_symbol(token_address, &l1_provider).await?
+                    }
+                    None => {
+                        get_base_token_decimals_symbol(
+                            l2_provider.get_base_token_l1_address().await?,
+                            &l1_provider,
+                        )
+                        .await?
+                    }
+                };
+                let amount = parse_amount(&amount, decimals, raw)?;
+                if tx_opts.estimate {
+                    let (max_fee_per_gas, _) = l1_provider.estimate_eip1559_fees(None).await?;
+                    let (tx_to, value, calldata) = l1_deposit_call(
+                        amount,
+                        token_address,
+                        zk_wallet.l1_address(),
+                        to.unwrap_or(zk_wallet.l2_address()),
+                        max_fee_per_gas,
+                        &cfg,
+                    )
+                    .await?;
+                    let deposit_tx: TypedTransaction = Eip1559TransactionRequest::new()
+                        .from(zk_wallet.l1_address())
+                        .to(tx_to)
+                        .value(value)
+                        .data(calldata)
+                        .into();
+                    let gas_limit = l1_provider.estimate_gas(&deposit_tx, None).await?;
+                    estimate_l1_fee(gas_limit, &l1_provider)
+                        .await?
+                        .display(18_i32, "ETH")?;
+                    println!(
+                        "L2 base cost: {} ETH",
+                        format_units(value.saturating_sub(amount), 18_i32)?
+                    );
+                }
+                if !confirm_amount("Deposit", amount, Some((decimals, &symbol)), tx_opts.yes)? {
+                    return Ok(());
+                }
+                if let Some(signed_tx_path) = &tx_opts.sign_opts.sign_only {
+                    // The L2 base cost is paid at the max fee per gas the deposit is signed with.
+                    let (estimated_max_fee_per_gas, estimated_max_priority_fee_per_gas) = match (
+                        tx_opts.sign_opts.max_fee_per_gas,
+                        tx_opts.sign_opts.max_priority_fee_per_gas,
+                    ) {
+                        (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => {
+                            (max_fee_per_gas, max_priority_fee_per_gas)
+                        }
+                        _ => l1_provider.estimate_eip1559_fees(None).await?,
+                    };
+                    let max_fee_per_gas = tx_opts
+                        .sign_opts
+                        .max_fee_per_gas
+                        .unwrap_or(estimated_max_fee_per_gas);
+                    let sign_opts = SignOpts {
+                        max_fee_per_gas: Some(max_fee_per_gas),
+                        max_priority_fee_per_gas: Some(
+                            tx_opts
+                                .sign_opts
+                                .max_priority_fee_per_gas
+                                .unwrap_or(estimated_max_priority_fee_per_gas),
+                        ),
+                        ..tx_opts.sign_opts.clone()
+                    };
+                    let (tx_to, value, calldata) = l1_deposit_call(
+                        amount,
+                        token_address,
+                        zk_wallet.l1_address(),
+                        to.unwrap_or(zk_wallet.l2_address()),
+                        max_fee_per_gas,
+                        &cfg,
+                    )
+                    .await?;
+                    sign_l1_transaction(
+                        zk_wallet.l1_signer().signer(),
+                        tx_to,
+                        value,
+                        calldata.into(),
+                        &l1_provider,
+                        &sign_opts,
+                    )
+                    .await?
+                    .write(signed_tx_path)?;
+                    println!("Once it is broadcast, follow it with `zks wallet deposit-status <l1_tx_hash>`");
+                    return Ok(());
+                }
                 let mut spinner: Spinner = Spinner::new(send_frames, "Depositing", Color::Cyan);
                 let deposit_hash = match (to, token_address) {
                     (None, None) => zk_wallet.deposit_base_token(amount).await?,
@@ -137,10 +441,58 @@ impl Command {
                 };
 
                 spinner.success(&msg);
+
+                if wait {
+                    let l2_receipt = wait_for_l2_deposit(
+                        deposit_hash,
+                        Duration::from_secs(timeout),
+                        &l1_provider,
+                        &l2_provider,
+                    )
+                    .await?;
+                    display_l2_deposit_result(&l2_receipt, &l2_explorer_url);
+                }
+            }
+            Command::DepositStatus {
+                l1_deposit_tx_hash,
+                timeout,
+            } => {
+                let l2_receipt = wait_for_l2_deposit(
+                    l1_deposit_tx_hash,
+                    Duration::from_secs(timeout),
+                    &l1_provider,
+                    &l2_provider,
+                )
+                .await?;
+                display_l2_deposit_result(&l2_receipt, &l2_explorer_url);
             }
             Command::FinalizeWithdraw {
-                l2_withdrawal_tx_hash,
+                l2_withdrawal_tx_hash: Some(l2_withdrawal_tx_hash),
+                sign_opts,
+                ..
             } => {
+                if let Some(signed_tx_path) = &sign_opts.sign_only {
+                    let status =
+                        withdrawal_status(l2_withdrawal_tx_hash, &l1_provider, &l2_provider)
+                            .await?;
+                    if status != WithdrawalStatus::Finalizable {
+                        eyre::bail!("{l2_withdrawal_tx_hash:?} can't be finalized: {status}");
+                    }
+                    let (tx_to, calldata) =
+                        finalize_withdrawal_call(l2_withdrawal_tx_hash, &l2_provider).await?;
+                    sign_l1_transaction(
+                        zk_wallet.l1_signer().signer(),
+                        tx_to,
+                        U256::zero(),
+                        calldata.into(),
+                        &l1_provider,
+                        &sign_opts,
+                    )
+                    .await?
+                    .write(signed_tx_path)?;
+                    println!("Once it is broadcast, stop tracking it with `zks wallet finalize-withdraw --all`");
+                    return Ok(());
+                }
                 let mut spinner: Spinner = Spinner::new(
                     recv_frames,
                     "Waiting for Withdrawal Finalization",
@@ -150,27 +502,185 @@ impl Command {
                     wait_for_finalize_withdrawal(l2_withdrawal_tx_hash, &l2_provider);
                 wait_withdraw.await;
                 let withdraw_hash = zk_wallet.finalize_withdraw(l2_withdrawal_tx_hash).await?;
+                remove_pending_withdrawal(l2_withdrawal_tx_hash)?;
                 let msg = format!("Success: {l1_explorer_url}/tx/{withdraw_hash:?}");
                 spinner.success(&msg);
             }
+            Command::FinalizeWithdraw {
+                l2_withdrawal_tx_hash: None,
+                ..
+            } => {
+                let l2_chain_id = l2_provider.get_chainid().await?.as_u64();
+                for withdrawal in pending_withdrawals_of(zk_wallet.l2_address(), l2_chain_id)? {
+                    let l2_tx_hash = withdrawal.l2_tx_hash;
+                    match withdrawal_status(l2_tx_hash, &l1_provider, &l2_provider).await? {
+                        WithdrawalStatus::Finalizable => {
+                            let withdraw_hash = zk_wallet.finalize_withdraw(l2_tx_hash).await?;
+                            remove_pending_withdrawal(l2_tx_hash)?;
+                            println!(
+                                "{l2_tx_hash:?} finalized: {l1_explorer_url}/tx/{withdraw_hash:?}"
+                            );
+                        }
+                        WithdrawalStatus::Finalized => {
+                            remove_pending_withdrawal(l2_tx_hash)?;
+                            println!("{l2_tx_hash:?} was already finalized");
+                        }
+                        WithdrawalStatus::Failed => {
+                            remove_pending_withdrawal(l2_tx_hash)?;
+                            println!("{l2_tx_hash:?} failed on L2, it is no longer tracked");
+                        }
+                        status => println!("{l2_tx_hash:?} not ready: {status}"),
+                    }
+                }
+            }
             Command::Transfer {
                 amount,
+                raw,
                 token_address,
                 to,
                 l1,
                 explorer_url,
+                paymaster,
+                tx_opts,
             } => {
+                let paymaster_params = paymaster.paymaster_params()?;
+                if l1 && paymaster_params.is_some() {
+                    eyre::bail!("Paymasters are only supported for L2 transfers");
+                }
+                if !l1 && (paymaster_params.is_some() || tx_opts.sign_opts.sign_only.is_some()) {
+                    zk_wallet.l2_signer().signer().ensure_typed_data_support()?;
+                }
+                // A raw amount is signed offline without looking up the token.
+                let offline_raw_amount = raw && tx_opts.sign_opts.sign_only.is_some();
+                // Fetched once for both the base token amount and the L2 fee estimation.
+                let base_token_units = if !l1
+                    && ((token_address.is_none() && !offline_raw_amount) || tx_opts.estimate)
+                {
+                    Some(
+                        get_base_token_decimals_symbol(
+                            l2_provider.get_base_token_l1_address().await?,
+                            &l1_provider,
+                        )
+                        .await?,
+                    )
+                } else {
+                    None
+                };
+                let token_units = match (l1, token_address) {
+                    _ if offline_raw_amount => None,
+                    (true, token_address) => {
+                        Some(get_token_decimals_symbol(token_address, &l1_provider).await?)
+                    }
+                    (false, Some(token_address)) => {
+                        Some(get_erc20_decimals_symbol(token_address, &l2_provider).await?)
+                    }
+                    (false, None) => base_token_units.clone(),
+                };
+                let amount = parse_amount(
+                    &amount,
+                    token_units
+                        .as_ref()
+                        .map_or(0_i32, |(decimals, _)| *decimals),
+                    raw,
+                )?;
+                if tx_opts.estimate {
+                    let (tx_to, value, calldata) = transfer_call(amount, token_address, to);
+                    if l1 {
+                        let transfer_tx: TypedTransaction = Eip1559TransactionRequest::new()
+                            .from(zk_wallet.l1_address())
+                            .to(tx_to)
+                            .value(value)
+                            .data(calldata)
+                            .into();
+                        let gas_limit = l1_provider.estimate_gas(&transfer_tx, None).await?;
+                        estimate_l1_fee(gas_limit, &l1_provider)
+                            .await?
+                            .display(18_i32, "ETH")?;
+                    } else {
+                        let (base_token_decimals, base_token_symbol) = base_token_units
+                            .as_ref()
+                            .context("Base token units not fetched")?;
+                        estimate_l2_fee(
+                            &l2_provider,
+                            zk_wallet.l2_address(),
+                            tx_to,
+                            value,
+                            &calldata,
+                            paymaster_params.as_ref(),
+                        )
+                        .await?
+                        .display(*base_token_decimals, base_token_symbol)?;
+                    }
+                }
+                if !confirm_amount(
+                    &format!("Transfer to {to:?}"),
+                    amount,
+                    token_units
+                        .as_ref()
+                        .map(|(decimals, symbol)| (*decimals, symbol.as_str())),
+                    tx_opts.yes,
+                )? {
+                    return Ok(());
+                }
+                if let Some(signed_tx_path) = &tx_opts.sign_opts.sign_only {
+                    let (tx_to, value, calldata) = transfer_call(amount, token_address, to);
+                    let signed_tx = if l1 {
+                        sign_l1_transaction(
+                            zk_wallet.l1_signer().signer(),
+                            tx_to,
+                            value,
+                            calldata.into(),
+                            &l1_provider,
+                            &tx_opts.sign_opts,
+                        )
+                        .await?
+                    } else {
+                        sign_l2_transaction(
+                            &zk_wallet,
+                            tx_to,
+                            value,
+                            calldata,
+                            paymaster_params,
+                            &tx_opts.sign_opts,
+                        )
+                        .await?
+                    };
+                    signed_tx.write(signed_tx_path)?;
+                    return Ok(());
+                }
                 if l1 {
-                    todo!("L1 transfers not supported by ZKWallet");
+                    let mut spinner: Spinner =
+                        Spinner::new(send_frames, "Transferring on L1", Color::Cyan);
+                    let transfer_hash = l1_transfer(&zk_wallet, amount, token_address, to).await?;
+
+                    let msg = if explorer_url {
+                        format!("Success: {l1_explorer_url}/tx/{transfer_hash:?}")
+                    } else {
+                        format!("Success, Transfer hash: {transfer_hash:?}")
+                    };
+
+                    spinner.success(&msg);
                 } else {
                     let mut spinner: Spinner =
                         Spinner::new(send_frames, "Transferring", Color::Cyan);
-                    let transfer_hash = if let Some(token_address) = token_address {
-                        zk_wallet
-                            .transfer_erc20(amount, token_address, to, None)
+                    let transfer_hash = match (paymaster_params, token_address) {
+                        (Some(paymaster_params), token_address) => {
+                            let (tx_to, value, calldata) = transfer_call(amount, token_address, to);
+                            send_l2_transaction_with_paymaster(
+                                &zk_wallet,
+                                tx_to,
+                                value,
+                                calldata,
+                                paymaster_params,
+                            )
                             .await?
-                    } else {
-                        zk_wallet.transfer_base_token(amount, to, None).await?
+                        }
+                        (None, Some(token_address)) => {
+                            zk_wallet
+                                .transfer_erc20(amount, token_address, to, None)
+                                .await?
+                        }
+                        (None, None) => zk_wallet.transfer_base_token(amount, to, None).await?,
                     };
 
                     let msg = if explorer_url {
@@ -184,28 +694,160 @@ impl Command {
             }
             Command::Withdraw {
                 amount,
+                raw,
                 token_address,
                 explorer_url,
+                wait,
+                paymaster,
+                tx_opts,
             } => {
-                let mut spinner: Spinner = Spinner::new(
-                    recv_frames,
-                    "Waiting for Withdrawal Finalization",
-                    Color::Cyan,
-                );
-                // TODO revise how to withdraw ETH
-                let l2_withdrawal_tx_hash = if let Some(token) = token_address {
-                    if token == base_token_address {
-                        zk_wallet.withdraw_base_token(amount).await?
+                let paymaster_params = paymaster.paymaster_params()?;
+                if paymaster_params.is_some() || tx_opts.sign_opts.sign_only.is_some() {
+                    zk_wallet.l2_signer().signer().ensure_typed_data_support()?;
+                }
+                // Withdrawing the base token by its address is a base token withdrawal.
+                let erc20_address = match token_address {
+                    Some(token_address)
+                        if token_address != l2_provider.get_base_token_l1_address().await? =>
+                    {
+                        Some(token_address)
+                    }
+                    _ => None,
+                };
+                // A raw amount is signed offline without looking up the token.
+                let offline_raw_amount = raw && tx_opts.sign_opts.sign_only.is_some();
+                // Fetched once for both the base token amount and the fee estimation.
+                let base_token_units =
+                    if (erc20_address.is_none() && !offline_raw_amount) || tx_opts.estimate {
+                        Some(
+                            get_base_token_decimals_symbol(
+                                l2_provider.get_base_token_l1_address().await?,
+                                &l1_provider,
+                            )
+                            .await?,
+                        )
                     } else {
-                        zk_wallet.withdraw_erc20(amount, token).await?
+                        None
+                    };
+                let token_units = match erc20_address {
+                    _ if offline_raw_amount => None,
+                    Some(token_address) => {
+                        Some(get_erc20_decimals_symbol(token_address, &l2_provider).await?)
                     }
+                    None => base_token_units.clone(),
+                };
+                let amount = parse_amount(
+                    &amount,
+                    token_units
+                        .as_ref()
+                        .map_or(0_i32, |(decimals, _)| *decimals),
+                    raw,
+                )?;
+                if tx_opts.estimate {
+                    let (tx_to, value, calldata) = l2_withdraw_call(
+                        amount,
+                        erc20_address,
+                        zk_wallet.l1_address(),
+                        &l2_provider,
+                    )
+                    .await?;
+                    let (base_token_decimals, base_token_symbol) = base_token_units
+                        .as_ref()
+                        .context("Base token units not fetched")?;
+                    estimate_l2_fee(
+                        &l2_provider,
+                        zk_wallet.l2_address(),
+                        tx_to,
+                        value,
+                        &calldata,
+                        paymaster_params.as_ref(),
+                    )
+                    .await?
+                    .display(*base_token_decimals, base_token_symbol)?;
+                }
+                if !confirm_amount(
+                    "Withdraw",
+                    amount,
+                    token_units
+                        .as_ref()
+                        .map(|(decimals, symbol)| (*decimals, symbol.as_str())),
+                    tx_opts.yes,
+                )? {
+                    return Ok(());
+                }
+                if let Some(signed_tx_path) = &tx_opts.sign_opts.sign_only {
+                    let (tx_to, value, calldata) = l2_withdraw_call(
+                        amount,
+                        erc20_address,
+                        zk_wallet.l1_address(),
+                        &l2_provider,
+                    )
+                    .await?;
+                    sign_l2_transaction(
+                        &zk_wallet,
+                        tx_to,
+                        value,
+                        calldata,
+                        paymaster_params,
+                        &tx_opts.sign_opts,
+                    )
+                    .await?
+                    .write(signed_tx_path)?;
+                    println!("Once it is broadcast and its batch executed, finalize it with `zks wallet finalize-withdraw --hash <l2_tx_hash>`");
+                    return Ok(());
+                }
+                let mut spinner: Spinner = Spinner::new(recv_frames, "Withdrawing", Color::Cyan);
+                // TODO revise how to withdraw ETH
+                let l2_withdrawal_tx_hash = if let Some(paymaster_params) = paymaster_params {
+                    let (tx_to, value, calldata) = l2_withdraw_call(
+                        amount,
+                        erc20_address,
+                        zk_wallet.l1_address(),
+                        &l2_provider,
+                    )
+                    .await?;
+                    send_l2_transaction_with_paymaster(
+                        &zk_wallet,
+                        tx_to,
+                        value,
+                        calldata,
+                        paymaster_params,
+                    )
+                    .await?
+                } else if let Some(token) = erc20_address {
+                    zk_wallet.withdraw_erc20(amount, token).await?
                 } else {
                     zk_wallet.withdraw_base_token(amount).await?
                 };
+                save_pending_withdrawal(PendingWithdrawal {
+                    l2_chain_id: l2_provider.get_chainid().await?.as_u64(),
+                    address: zk_wallet.l2_address(),
+                    l2_tx_hash: l2_withdrawal_tx_hash,
+                    token_address,
+                    amount,
+                })?;
+
+                if !wait {
+                    let msg = if explorer_url {
+                        format!("Success: {l2_explorer_url}/tx/{l2_withdrawal_tx_hash:?}")
+                    } else {
+                        format!("Success, L2 Withdraw hash: {l2_withdrawal_tx_hash:?}")
+                    };
+                    spinner.success(&msg);
+                    println!("Track it with `zks wallet withdrawals`, and finalize it once its batch is executed with `zks wallet finalize-withdraw --all`");
+                    return Ok(());
+                }
+
+                spinner.update(
+                    recv_frames,
+                    "Waiting for Withdrawal Finalization",
+                    Color::Cyan,
+                );
                 let wait_withdraw =
                     wait_for_finalize_withdrawal(l2_withdrawal_tx_hash, &l2_provider);
                 wait_withdraw.await;
                 let withdraw_hash = zk_wallet.finalize_withdraw(l2_withdrawal_tx_hash).await?;
+                remove_pending_withdrawal(l2_withdrawal_tx_hash)?;
 
                 let msg = if explorer_url {
                     format!("Success: {l1_explorer_url}/tx/{withdraw_hash:?}")
@@ -215,12 +857,53 @@ impl Command {
 
                 spinner.success(&msg);
             }
+            Command::Withdrawals => {
+                let l2_chain_id = l2_provider.get_chainid().await?.as_u64();
+                let withdrawals = pending_withdrawals_of(zk_wallet.l2_address(), l2_chain_id)?;
+                if withdrawals.is_empty() {
+                    println!("No tracked withdrawals");
+                }
+                for withdrawal in withdrawals {
+                    let token = withdrawal
+                        .token_address
+                        .map_or("base token".to_owned(), |token| format!("{token:?}"));
+                    let status =
+                        match withdrawal_status(withdrawal.l2_tx_hash, &l1_provider, &l2_provider)
+                            .await
+                        {
+                            // Failed withdrawals will never be finalizable.
+                            Ok(WithdrawalStatus::Failed) => {
+                                remove_pending_withdrawal(withdrawal.l2_tx_hash)?;
+                                format!("{}, no longer tracked", WithdrawalStatus::Failed)
+                            }
+                            Ok(status) => status.to_string(),
+                            Err(e) => format!("Unknown, failed to get its status: {e}"),
+                        };
+                    println!(
+                        "{:?}: {} of {token} || {status}",
+                        withdrawal.l2_tx_hash, withdrawal.amount
+                    );
+                }
+            }
             Command::Address => {
                 println!("Wallet address: {:?}", wallet_config.address);
             }
-            Command::PrivateKey => {
-                println!("Wallet private key: {:?}", wallet_config.private_key);
-            }
+            Command::PrivateKey => match wallet_config.private_key {
+                Some(private_key) => println!("Wallet private key: {private_key:?}"),
+                None if confirm(WALLET_EXPORT_PRIVATE_KEY_CONFIRMATION_MSG)? => {
+                    let wallet = wallet_config.signer()?;
+                    println!(
+                        "Wallet private key: 0x{}",
+                        hex::encode(wallet.signer().to_bytes())
+                    );
+                }
+                None => (),
+            },
+            Command::New { .. }
+            | Command::Import { .. }
+            | Command::Export { .. }
+            | Command::Keystores
+            | Command::List => unreachable!(),
         };
 
         Ok(())
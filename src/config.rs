@@ -1,20 +1,91 @@
-use crate::{commands, utils::config::selected_config_path};
+use crate::{
+    commands,
+    utils::{
+        config::{default_values::DEFAULT_MAX_ATTEMPTS, selected_config_path},
+        db::types::StuckJob,
+        keystore::decrypt_keystore,
+        signer::{ConfigSigner, RemoteSigner},
+        wallet::derive_wallet,
+    },
+};
+use chrono::{Duration, Utc};
+use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
 use eyre::Context;
 use serde::{de, Deserialize, Serialize};
 use std::{
+    collections::BTreeMap,
     fmt::Display,
     ops::{Deref, DerefMut},
     str::FromStr,
 };
-use zksync_ethers_rs::types::{zksync::url::SensitiveUrl, Address};
+use zksync_ethers_rs::{
+    signers::{LocalWallet, Signer},
+    types::{
+        zksync::{basic_fri_types::AggregationRound, url::SensitiveUrl},
+        Address,
+    },
+};
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct ZKSyncConfig {
     pub network: NetworkConfig,
     pub wallet: Option<WalletConfig>,
+    /// Named wallets, selected with the global `--wallet <alias>` flag.
+    #[serde(default)]
+    pub wallets: BTreeMap<String, WalletConfig>,
+    /// Alias of the wallet used when no `--wallet` is given, `wallet` is used if not set.
+    pub default_wallet: Option<String>,
+    /// Set from the global `--wallet` flag, never stored.
+    #[serde(skip)]
+    pub selected_wallet: Option<String>,
     pub db: Option<DatabaseConfig>,
     pub governance: GovernanceConfig,
     pub bridgehub: BridgehubConfig,
+    #[serde(default)]
+    pub stuck_jobs: StuckJobsPolicy,
+}
+
+/// Alias the unnamed `wallet` is listed and selected with.
+pub const DEFAULT_WALLET_ALIAS: &str = "default";
+
+impl ZKSyncConfig {
+    /// The wallet selected with `--wallet`, or the default one.
+    pub fn wallet_config(&self) -> eyre::Result<&WalletConfig> {
+        match self
+            .selected_wallet
+            .as_ref()
+            .or(self.default_wallet.as_ref())
+        {
+            Some(alias) => self
+                .wallets
+                .get(alias)
+                .or_else(|| {
+                    self.wallet
+                        .as_ref()
+                        .filter(|_| alias == DEFAULT_WALLET_ALIAS)
+                })
+                .ok_or_else(|| eyre::eyre!("Wallet {alias} not found in config")),
+            None => self
+                .wallet
+                .as_ref()
+                .ok_or_else(|| eyre::eyre!("Wallet config missing")),
+        }
+    }
+
+    /// The configured wallets by alias, the unnamed `wallet` included as `default` unless a
+    /// named wallet takes that alias.
+    pub fn wallet_configs(&self) -> Vec<(&str, &WalletConfig)> {
+        self.wallet
+            .iter()
+            .filter(|_| !self.wallets.contains_key(DEFAULT_WALLET_ALIAS))
+            .map(|wallet_config| (DEFAULT_WALLET_ALIAS, wallet_config))
+            .chain(
+                self.wallets
+                    .iter()
+                    .map(|(alias, wallet_config)| (alias.as_str(), wallet_config)),
+            )
+            .collect()
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -30,19 +101,197 @@ pub struct NetworkConfig {
 #[derive(Deserialize, Serialize, Clone)]
 pub struct WalletConfig {
     pub address: Address,
-    pub private_key: String,
+    pub private_key: Option<String>,
+    /// Name of an encrypted keystore in `~/.config/zks-cli/keystores`, used if no
+    /// `private_key` is set.
+    pub keystore: Option<String>,
+    /// BIP-39 mnemonic, used if neither `private_key` nor `keystore` are set.
+    pub mnemonic: Option<String>,
+    /// Index in the `m/44'/60'/0'/0/{index}` derivation path of `mnemonic`, defaults to 0.
+    pub derivation_index: Option<u32>,
+    /// JSON-RPC endpoint of a remote signer holding the key of `address`, used if no
+    /// `private_key`, `keystore` nor `mnemonic` are set.
+    pub remote_signer_url: Option<String>,
+}
+
+impl WalletConfig {
+    pub fn signer(&self) -> eyre::Result<LocalWallet> {
+        match (&self.private_key, &self.keystore, &self.mnemonic) {
+            (Some(private_key), _, _) => private_key.parse().map_err(Into::into),
+            (None, Some(keystore_name), _) => {
+                let wallet = decrypt_keystore(keystore_name)?;
+                // A swapped or misnamed keystore would sign as another account.
+                if wallet.address() != self.address {
+                    eyre::bail!(
+                        "Keystore {keystore_name} holds the key of {:?}, not of the wallet address {:?}",
+                        wallet.address(),
+                        self.address
+                    );
+                }
+                Ok(wallet)
+            }
+            (None, None, Some(mnemonic)) => {
+                derive_wallet(mnemonic, self.derivation_index.unwrap_or_default())
+            }
+            (None, None, None) if self.remote_signer_url.is_some() => {
+                eyre::bail!(
+                    "Wallet {:?} uses a remote signer, its key is not available locally",
+                    self.address
+                )
+            }
+            (None, None, None) => {
+                eyre::bail!("Wallet config has neither a private key, a keystore, a mnemonic nor a remote signer")
+            }
+        }
+    }
+
+    /// The wallet signer, its remote signer if it has no local key.
+    pub fn config_signer(&self) -> eyre::Result<ConfigSigner> {
+        match (
+            &self.private_key,
+            &self.keystore,
+            &self.mnemonic,
+            &self.remote_signer_url,
+        ) {
+            (None, None, None, Some(remote_signer_url)) => Ok(ConfigSigner::Remote(
+                RemoteSigner::new(remote_signer_url, self.address)?,
+            )),
+            _ => self.signer().map(ConfigSigner::Local),
+        }
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct GovernanceConfig {
     pub address: Address,
     pub owner_private_key: String,
+    /// Safe owning the Governance, its transactions are sent through the Safe if set and
+    /// `owner_private_key` is one of the Safe owners.
+    pub owner_safe: Option<Address>,
+    /// Key of the security council, the only account able to execute operations instantly.
+    pub security_council_private_key: Option<String>,
+    /// Alias of the configured wallet being the security council, used if no
+    /// `security_council_private_key` is set.
+    pub security_council_wallet: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
 pub struct BridgehubConfig {
+    /// Bridgehub address, fetched from the L2 node if not set, e.g. to sign offline.
+    pub address: Option<Address>,
     pub admin_private_key: Option<String>,
     pub owner_private_key: Option<String>,
+    /// Safe being the Bridgehub admin, see `GovernanceConfig::owner_safe`.
+    pub admin_safe: Option<Address>,
+    /// Safe owning the Bridgehub, see `GovernanceConfig::owner_safe`.
+    pub owner_safe: Option<Address>,
+}
+
+/// When a prover job is considered stuck. A failed or in progress job is stuck once it
+/// reaches the max attempts of its stage, and an in progress job is also stuck once it has
+/// been processing for longer than the max in progress duration of its circuit.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct StuckJobsPolicy {
+    pub max_attempts: u32,
+    pub stage_max_attempts: StageMaxAttempts,
+    /// Disabled if not set.
+    pub max_in_progress_secs: Option<u64>,
+    /// Keyed by `BaseLayerCircuitType` name, e.g. `VM` or `EIP4844Repack`.
+    #[serde(deserialize_with = "deserialize_circuit_max_in_progress_secs")]
+    pub circuit_max_in_progress_secs: BTreeMap<String, u64>,
+}
+
+/// Rejects the keys not naming a `BaseLayerCircuitType`, which would never match a job.
+fn deserialize_circuit_max_in_progress_secs<'de, D>(
+    deserializer: D,
+) -> Result<BTreeMap<String, u64>, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let circuit_max_in_progress_secs = BTreeMap::<String, u64>::deserialize(deserializer)?;
+    let circuit_names: Vec<String> = (1..=15_u8)
+        .chain([255])
+        .map(|circuit_id| format!("{:?}", BaseLayerCircuitType::from_numeric_value(circuit_id)))
+        .collect();
+    if let Some(unknown_circuit) = circuit_max_in_progress_secs
+        .keys()
+        .find(|circuit| !circuit_names.contains(circuit))
+    {
+        return Err(de::Error::custom(format!(
+            "Unknown circuit {unknown_circuit} in circuit_max_in_progress_secs, expected one of {}",
+            circuit_names.join(", ")
+        )));
+    }
+    Ok(circuit_max_in_progress_secs)
+}
+
+/// Overrides `StuckJobsPolicy::max_attempts` for a single stage.
+#[derive(Deserialize, Serialize, Clone, Default)]
+#[serde(default)]
+pub struct StageMaxAttempts {
+    pub basic_circuits: Option<u32>,
+    pub leaf_aggregation: Option<u32>,
+    pub node_aggregation: Option<u32>,
+    pub recursion_tip: Option<u32>,
+    pub scheduler: Option<u32>,
+    pub compressor: Option<u32>,
+}
+
+impl Default for StuckJobsPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            stage_max_attempts: StageMaxAttempts::default(),
+            max_in_progress_secs: None,
+            circuit_max_in_progress_secs: BTreeMap::new(),
+        }
+    }
+}
+
+impl StuckJobsPolicy {
+    /// `None` stands for the compressor, which has no aggregation round.
+    pub fn max_attempts_for(&self, aggregation_round: Option<AggregationRound>) -> u32 {
+        let stage_max_attempts = &self.stage_max_attempts;
+        match aggregation_round {
+            Some(AggregationRound::BasicCircuits) => stage_max_attempts.basic_circuits,
+            Some(AggregationRound::LeafAggregation) => stage_max_attempts.leaf_aggregation,
+            Some(AggregationRound::NodeAggregation) => stage_max_attempts.node_aggregation,
+            Some(AggregationRound::RecursionTip) => stage_max_attempts.recursion_tip,
+            Some(AggregationRound::Scheduler) => stage_max_attempts.scheduler,
+            None => stage_max_attempts.compressor,
+        }
+        .unwrap_or(self.max_attempts)
+    }
+
+    pub fn max_in_progress_for(
+        &self,
+        circuit_type: Option<BaseLayerCircuitType>,
+    ) -> Option<Duration> {
+        circuit_type
+            .and_then(|circuit_type| {
+                self.circuit_max_in_progress_secs
+                    .get(&format!("{circuit_type:?}"))
+                    .copied()
+            })
+            .or(self.max_in_progress_secs)
+            .and_then(|secs| i64::try_from(secs).ok())
+            .map(Duration::seconds)
+    }
+
+    pub fn is_stuck(&self, job: &impl StuckJob) -> bool {
+        let out_of_attempts = (job.is_failed() || job.is_in_progress())
+            && job.attempts() >= self.max_attempts_for(job.aggregation_round());
+        // The prover DB stores timestamps in UTC.
+        let in_progress_for_too_long = job.is_in_progress()
+            && self
+                .max_in_progress_for(job.base_layer_circuit_type())
+                .zip(job.processing_started_at())
+                .is_some_and(|(max_in_progress, processing_started_at)| {
+                    Utc::now().naive_utc() - processing_started_at > max_in_progress
+                });
+        out_of_attempts || in_progress_for_too_long
+    }
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -141,3 +390,118 @@ pub async fn load_selected_config() -> eyre::Result<ZKSyncConfig> {
     let config = std::fs::read_to_string(config_path).context("Failed to read config file")?;
     toml::from_str(&config).context("Failed to parse config file")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::StuckJobsPolicy;
+    use crate::utils::db::types::StuckJob;
+    use chrono::{Duration, NaiveDateTime, Utc};
+    use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
+    use zksync_ethers_rs::types::zksync::basic_fri_types::AggregationRound;
+
+    const VM_CIRCUIT_ID: u8 = 1;
+    const DECOMMITTER_CIRCUIT_ID: u8 = 3;
+
+    struct Job {
+        failed: bool,
+        in_progress: bool,
+        attempts: u32,
+        processing_for: Duration,
+        circuit_id: u8,
+    }
+
+    impl StuckJob for Job {
+        fn is_failed(&self) -> bool {
+            self.failed
+        }
+
+        fn is_in_progress(&self) -> bool {
+            self.in_progress
+        }
+
+        fn attempts(&self) -> u32 {
+            self.attempts
+        }
+
+        fn processing_started_at(&self) -> Option<NaiveDateTime> {
+            Some(Utc::now().naive_utc() - self.processing_for)
+        }
+
+        fn aggregation_round(&self) -> Option<AggregationRound> {
+            Some(AggregationRound::BasicCircuits)
+        }
+
+        fn base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+            Some(BaseLayerCircuitType::from_numeric_value(self.circuit_id))
+        }
+    }
+
+    fn in_progress_job(attempts: u32, processing_for: Duration) -> Job {
+        Job {
+            failed: false,
+            in_progress: true,
+            attempts,
+            processing_for,
+            circuit_id: VM_CIRCUIT_ID,
+        }
+    }
+
+    #[test]
+    fn jobs_are_stuck_once_out_of_attempts() -> eyre::Result<()> {
+        let policy: StuckJobsPolicy =
+            toml::from_str("max_attempts = 3\n[stage_max_attempts]\nbasic_circuits = 5")?;
+        let failed_job = |attempts| Job {
+            failed: true,
+            in_progress: false,
+            ..in_progress_job(attempts, Duration::zero())
+        };
+        assert!(!policy.is_stuck(&failed_job(4)));
+        assert!(policy.is_stuck(&failed_job(5)));
+        assert!(!policy.is_stuck(&in_progress_job(4, Duration::zero())));
+        assert!(policy.is_stuck(&in_progress_job(5, Duration::zero())));
+        let queued_job = Job {
+            in_progress: false,
+            ..in_progress_job(5, Duration::zero())
+        };
+        assert!(!policy.is_stuck(&queued_job));
+        Ok(())
+    }
+
+    #[test]
+    fn jobs_are_stuck_once_in_progress_for_too_long() -> eyre::Result<()> {
+        let policy = StuckJobsPolicy::default();
+        assert!(!policy.is_stuck(&in_progress_job(1, Duration::days(30))));
+
+        let policy: StuckJobsPolicy = toml::from_str(
+            "max_in_progress_secs = 3600\n[circuit_max_in_progress_secs]\nVM = 7200",
+        )?;
+        assert!(!policy.is_stuck(&in_progress_job(1, Duration::minutes(90))));
+        assert!(policy.is_stuck(&in_progress_job(1, Duration::minutes(150))));
+        let other_circuit_job = Job {
+            circuit_id: DECOMMITTER_CIRCUIT_ID,
+            ..in_progress_job(1, Duration::minutes(90))
+        };
+        assert!(policy.is_stuck(&other_circuit_job));
+        let failed_job = Job {
+            failed: true,
+            in_progress: false,
+            ..in_progress_job(1, Duration::minutes(150))
+        };
+        assert!(!policy.is_stuck(&failed_job));
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_circuits_are_rejected() {
+        assert!(
+            toml::from_str::<StuckJobsPolicy>("[circuit_max_in_progress_secs]\nVM = 1").is_ok()
+        );
+        assert!(toml::from_str::<StuckJobsPolicy>(
+            "[circuit_max_in_progress_secs]\nEIP4844Repack = 1"
+        )
+        .is_ok());
+        assert!(
+            toml::from_str::<StuckJobsPolicy>("[circuit_max_in_progress_secs]\nVm = 1").is_err()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use crate::utils::config::confirm;
+use colored::Colorize;
+use zksync_ethers_rs::{
+    core::utils::{format_units, parse_units, Units},
+    types::U256,
+};
+
+const ETHER_DECIMALS: i32 = 18;
+
+/// Parses a token amount, e.g. `1.5` using the token decimals, `1.5gwei` using the given
+/// unit, or a raw amount in the token smallest unit if `raw` is set. Units are ether units,
+/// so they are only accepted for 18 decimals tokens.
+pub(crate) fn parse_amount(amount: &str, decimals: i32, raw: bool) -> eyre::Result<U256> {
+    let amount = amount.trim();
+    if amount.starts_with('-') {
+        eyre::bail!("Amount {amount} can't be negative");
+    }
+    if raw {
+        return U256::from_dec_str(amount)
+            .map_err(|e| eyre::eyre!("Invalid raw amount {amount}: {e}"));
+    }
+    let (value, unit) = amount.split_at(
+        amount
+            .find(|c: char| c.is_ascii_alphabetic())
+            .unwrap_or(amount.len()),
+    );
+    let parsed_amount = if unit.is_empty() {
+        parse_units(value, decimals)?
+    } else {
+        if decimals != ETHER_DECIMALS {
+            eyre::bail!(
+                "Amount {amount} has a unit but the token has {decimals} decimals, units are only supported for {ETHER_DECIMALS} decimals tokens"
+            );
+        }
+        let unit: Units = unit.parse()?;
+        parse_units(value.trim(), unit)?
+    };
+    Ok(parsed_amount.into())
+}
+
+/// Shows how the amount was interpreted and asks for confirmation, unless `yes` is set.
+/// Without the token decimals and symbol, only the amount in the token smallest unit is shown.
+pub(crate) fn confirm_amount(
+    action: &str,
+    amount: U256,
+    token_units: Option<(i32, &str)>,
+    yes: bool,
+) -> eyre::Result<bool> {
+    match token_units {
+        Some((decimals, symbol)) => println!(
+            "{action} {} {symbol} ({amount} in the token smallest unit)",
+            format_units(amount, decimals)?.bold()
+        ),
+        None => println!(
+            "{action} {} in the token smallest unit",
+            amount.to_string().bold()
+        ),
+    }
+    if yes {
+        return Ok(true);
+    }
+    let confirmed = confirm("Do you want to continue?")?;
+    if !confirmed {
+        println!("Aborted");
+    }
+    Ok(confirmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_amount;
+    use zksync_ethers_rs::types::U256;
+
+    #[test]
+    fn amounts_use_the_token_decimals() -> eyre::Result<()> {
+        assert_eq!(
+            parse_amount("1.5", 18, false)?,
+            U256::from(1_500_000_000_000_000_000_u128)
+        );
+        assert_eq!(parse_amount("1.5", 6, false)?, U256::from(1_500_000_u64));
+        assert_eq!(parse_amount(" 2 ", 0, false)?, U256::from(2_u64));
+        assert!(parse_amount("-1", 18, false).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn raw_amounts_are_in_the_smallest_unit() -> eyre::Result<()> {
+        assert_eq!(parse_amount("1500", 6, true)?, U256::from(1500_u64));
+        assert!(parse_amount("1.5", 6, true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn units_are_only_supported_for_18_decimals_tokens() -> eyre::Result<()> {
+        assert_eq!(
+            parse_amount("1.5gwei", 18, false)?,
+            U256::from(1_500_000_000_u64)
+        );
+        assert_eq!(
+            parse_amount("2 ether", 18, false)?,
+            U256::from(2_000_000_000_000_000_000_u128)
+        );
+        assert_eq!(parse_amount("7wei", 18, false)?, U256::from(7_u64));
+        assert!(parse_amount("1gwei", 6, false).is_err());
+        assert!(parse_amount("1 lightyear", 18, false).is_err());
+        Ok(())
+    }
+}
@@ -1,12 +1,10 @@
+use crate::utils::wallet::ETH_TOKEN_ADDRESS;
 use std::sync::Arc;
 use zksync_ethers_rs::{
     contracts::{erc20::ERC20, l2_shared_bridge::get_l2_token_from_l1_address},
-    core::{
-        k256::ecdsa::SigningKey,
-        utils::{format_ether, format_units},
-    },
+    core::utils::{format_ether, format_units},
     providers::{Http, Middleware, Provider},
-    signers::Wallet,
+    signers::Signer,
     types::Address,
     utils::L2_ETH_TOKEN_ADDRESS,
     zk_wallet::ZKWallet,
@@ -36,6 +34,28 @@ pub(crate) async fn get_erc20_decimals_symbol(
     Ok((token_decimals, token_symbol))
 }
 
+/// Decimals and symbol of an ERC20, or of ETH if no token is given.
+pub(crate) async fn get_token_decimals_symbol(
+    token_address: Option<Address>,
+    provider: &Provider<Http>,
+) -> eyre::Result<(i32, String)> {
+    match token_address {
+        Some(token_address) => get_erc20_decimals_symbol(token_address, provider).await,
+        None => Ok((18_i32, "ETH".to_owned())),
+    }
+}
+
+pub(crate) async fn get_base_token_decimals_symbol(
+    base_token_address: Address,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<(i32, String)> {
+    if base_token_address == ETH_TOKEN_ADDRESS {
+        Ok((18_i32, "ETH".to_owned()))
+    } else {
+        get_erc20_decimals_symbol(base_token_address, l1_provider).await
+    }
+}
+
 pub(crate) async fn get_erc20_balance(
     token_address: Address,
     wallet_address: Address,
@@ -111,9 +131,9 @@ pub(crate) async fn display_l2_balance(
     Ok(())
 }
 
-pub(crate) async fn display_balance(
+pub(crate) async fn display_balance<S: Signer>(
     token: Option<Address>,
-    wallet: &ZKWallet<Provider<Http>, Wallet<SigningKey>>,
+    wallet: &ZKWallet<Provider<Http>, S>,
     l1: bool,
     l2: bool,
 ) -> eyre::Result<()> {
@@ -137,3 +157,20 @@ pub(crate) async fn display_balance(
     };
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{get_base_token_decimals_symbol, ETH_TOKEN_ADDRESS};
+    use zksync_ethers_rs::providers::{Http, Provider};
+
+    #[tokio::test]
+    async fn eth_base_token_does_not_query_the_l1() -> eyre::Result<()> {
+        // Nothing listens on this port, any ERC20 call would fail.
+        let l1_provider = Provider::<Http>::try_from("http://127.0.0.1:1")?;
+        let (decimals, symbol) =
+            get_base_token_decimals_symbol(ETH_TOKEN_ADDRESS, &l1_provider).await?;
+        assert_eq!(decimals, 18);
+        assert_eq!(symbol, "ETH");
+        Ok(())
+    }
+}
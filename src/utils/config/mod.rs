@@ -1,8 +1,8 @@
 use crate::{
     commands::{self, config::EditConfigOpts},
     config::{
-        BridgehubConfig, DatabaseConfig, GovernanceConfig, NetworkConfig, WalletConfig,
-        ZKSyncConfig,
+        BridgehubConfig, DatabaseConfig, GovernanceConfig, NetworkConfig, StuckJobsPolicy,
+        WalletConfig, ZKSyncConfig,
     },
     utils::messages::{
         ADDRESS_PROMPT_MSG, CONFIG_CREATE_PROMPT_MSG, CONFIG_EDIT_PROMPT_MSG,
@@ -16,7 +16,7 @@ use crate::{
 };
 use dialoguer::{theme::ColorfulTheme, Confirm, Input, Select};
 use eyre::ContextCompat;
-use std::{path::PathBuf, str::FromStr};
+use std::{collections::BTreeMap, path::PathBuf, str::FromStr};
 
 pub mod default_values;
 use default_values::{
@@ -27,15 +27,21 @@ use default_values::{
 
 pub const SELECTED_CONFIG_FILE_NAME: &str = ".selected";
 
-pub fn configs_dir_path() -> eyre::Result<std::path::PathBuf> {
-    let configs_dir_path = dirs::config_dir()
+/// Returns the `subdir` of the zks-cli directory in the user's config directory, creating it
+/// if needed. An empty `subdir` returns the zks-cli directory itself.
+pub fn zks_dir_path(subdir: &str) -> eyre::Result<std::path::PathBuf> {
+    let zks_dir_path = dirs::config_dir()
         .ok_or_else(|| eyre::eyre!("Could not find user's config directory"))?
         .join("zks-cli")
-        .join("configs");
-    if !configs_dir_path.exists() {
-        std::fs::create_dir_all(&configs_dir_path)?;
+        .join(subdir);
+    if !zks_dir_path.exists() {
+        std::fs::create_dir_all(&zks_dir_path)?;
     }
-    Ok(configs_dir_path)
+    Ok(zks_dir_path)
+}
+
+pub fn configs_dir_path() -> eyre::Result<std::path::PathBuf> {
+    zks_dir_path("configs")
 }
 
 pub fn config_path(config_name: &str) -> eyre::Result<std::path::PathBuf> {
@@ -107,8 +113,12 @@ pub fn prompt_zksync_config() -> eyre::Result<ZKSyncConfig> {
                 .ok(),
         },
         wallet: Some(WalletConfig {
-            private_key: prompt(PRIVATE_KEY_PROMPT_MSG, DEFAULT_PRIVATE_KEY.into())?,
+            private_key: Some(prompt(PRIVATE_KEY_PROMPT_MSG, DEFAULT_PRIVATE_KEY.into())?),
             address: prompt(ADDRESS_PROMPT_MSG, DEFAULT_ADDRESS)?,
+            keystore: None,
+            mnemonic: None,
+            derivation_index: None,
+            remote_signer_url: None,
         }),
         governance: GovernanceConfig {
             address: prompt(CONTRACTS_GOVERNANCE_PROMPT_MSG, DEFAULT_CONTRACT_ADDRESS)?,
@@ -116,8 +126,12 @@ pub fn prompt_zksync_config() -> eyre::Result<ZKSyncConfig> {
                 CONTRACTS_GOVERNANCE_PRIVATE_KEY_PROMPT_MSG,
                 DEFAULT_PRIVATE_KEY.into(),
             )?,
+            owner_safe: None,
+            security_council_private_key: None,
+            security_council_wallet: None,
         },
         bridgehub: BridgehubConfig {
+            address: None,
             admin_private_key: prompt(
                 CONTRACTS_BRIDGEHUB_ADMIN_PRIVATE_KEY_PROMPT_MSG,
                 DEFAULT_PRIVATE_KEY.into(),
@@ -128,6 +142,8 @@ pub fn prompt_zksync_config() -> eyre::Result<ZKSyncConfig> {
                 DEFAULT_PRIVATE_KEY.into(),
             )
             .ok(),
+            admin_safe: None,
+            owner_safe: None,
         },
         db: Some(DatabaseConfig {
             server: prompt(
@@ -139,6 +155,10 @@ pub fn prompt_zksync_config() -> eyre::Result<ZKSyncConfig> {
                 DEFAULT_DATABASE_PROVER_URL.try_into()?,
             )?,
         }),
+        wallets: BTreeMap::new(),
+        default_wallet: None,
+        selected_wallet: None,
+        stuck_jobs: StuckJobsPolicy::default(),
     };
     Ok(prompted_config)
 }
@@ -250,24 +270,37 @@ pub fn edit_existing_config_interactively(
             )
             .ok(),
         },
-        wallet: Some(WalletConfig {
-            private_key: prompt(
-                PRIVATE_KEY_PROMPT_MSG,
-                existing_config
-                    .wallet
-                    .as_ref()
-                    .map(|w| w.private_key.clone())
-                    .unwrap_or(DEFAULT_PRIVATE_KEY.into()),
-            )?,
-            address: prompt(
-                ADDRESS_PROMPT_MSG,
-                existing_config
-                    .wallet
-                    .as_ref()
-                    .map(|w| w.address)
-                    .unwrap_or(DEFAULT_ADDRESS),
-            )?,
-        }),
+        wallet: match existing_config.wallet {
+            // Keystore, mnemonic and remote signer backed wallets are managed with `zks wallet`
+            // and `zks config edit` flags.
+            Some(existing_wallet_config)
+                if existing_wallet_config.keystore.is_some()
+                    || existing_wallet_config.mnemonic.is_some()
+                    || existing_wallet_config.remote_signer_url.is_some() =>
+            {
+                Some(existing_wallet_config)
+            }
+            existing_wallet_config => Some(WalletConfig {
+                private_key: Some(prompt(
+                    PRIVATE_KEY_PROMPT_MSG,
+                    existing_wallet_config
+                        .as_ref()
+                        .and_then(|w| w.private_key.clone())
+                        .unwrap_or(DEFAULT_PRIVATE_KEY.into()),
+                )?),
+                address: prompt(
+                    ADDRESS_PROMPT_MSG,
+                    existing_wallet_config
+                        .as_ref()
+                        .map(|w| w.address)
+                        .unwrap_or(DEFAULT_ADDRESS),
+                )?,
+                keystore: None,
+                mnemonic: None,
+                derivation_index: None,
+                remote_signer_url: None,
+            }),
+        },
         governance: GovernanceConfig {
             address: prompt(
                 CONTRACTS_GOVERNANCE_PROMPT_MSG,
@@ -277,8 +310,12 @@ pub fn edit_existing_config_interactively(
                 CONTRACTS_GOVERNANCE_PRIVATE_KEY_PROMPT_MSG,
                 existing_config.governance.owner_private_key,
             )?,
+            owner_safe: existing_config.governance.owner_safe,
+            security_council_private_key: existing_config.governance.security_council_private_key,
+            security_council_wallet: existing_config.governance.security_council_wallet,
         },
         bridgehub: BridgehubConfig {
+            address: existing_config.bridgehub.address,
             admin_private_key: prompt(
                 CONTRACTS_BRIDGEHUB_ADMIN_PRIVATE_KEY_PROMPT_MSG,
                 existing_config
@@ -295,6 +332,8 @@ pub fn edit_existing_config_interactively(
                     .unwrap_or(DEFAULT_PRIVATE_KEY.into()),
             )
             .ok(),
+            admin_safe: existing_config.bridgehub.admin_safe,
+            owner_safe: existing_config.bridgehub.owner_safe,
         },
         db: Some(DatabaseConfig {
             server: prompt(
@@ -317,6 +356,10 @@ pub fn edit_existing_config_interactively(
                     .try_into()?,
             )?,
         }),
+        wallets: existing_config.wallets,
+        default_wallet: existing_config.default_wallet,
+        selected_wallet: None,
+        stuck_jobs: existing_config.stuck_jobs,
     };
     Ok(config)
 }
@@ -340,14 +383,40 @@ pub fn edit_existing_config_non_interactively(
                 .l1_explorer_url
                 .or(existing_config.network.l1_explorer_url),
         },
-        wallet: existing_config
-            .wallet
-            .map(|existing_wallet_config| WalletConfig {
-                private_key: opts
-                    .private_key
-                    .unwrap_or(existing_wallet_config.private_key),
+        wallet: existing_config.wallet.map(|existing_wallet_config| {
+            // Setting either a private key, a keystore, a mnemonic or a remote signer replaces
+            // the others.
+            let (private_key, keystore, mnemonic, derivation_index, remote_signer_url) = match (
+                opts.private_key,
+                opts.keystore,
+                opts.mnemonic,
+                opts.remote_signer_url,
+            ) {
+                (None, None, None, None) => (
+                    existing_wallet_config.private_key,
+                    existing_wallet_config.keystore,
+                    existing_wallet_config.mnemonic,
+                    opts.derivation_index
+                        .or(existing_wallet_config.derivation_index),
+                    existing_wallet_config.remote_signer_url,
+                ),
+                (private_key, keystore, mnemonic, remote_signer_url) => (
+                    private_key,
+                    keystore,
+                    mnemonic,
+                    opts.derivation_index,
+                    remote_signer_url,
+                ),
+            };
+            WalletConfig {
+                private_key,
                 address: opts.address.unwrap_or(existing_wallet_config.address),
-            }),
+                keystore,
+                mnemonic,
+                derivation_index,
+                remote_signer_url,
+            }
+        }),
         governance: GovernanceConfig {
             address: opts
                 .governance
@@ -355,19 +424,39 @@ pub fn edit_existing_config_non_interactively(
             owner_private_key: opts
                 .governance_owner
                 .unwrap_or(existing_config.governance.owner_private_key),
+            owner_safe: opts
+                .governance_owner_safe
+                .or(existing_config.governance.owner_safe),
+            security_council_private_key: opts
+                .governance_security_council
+                .or(existing_config.governance.security_council_private_key),
+            security_council_wallet: opts
+                .governance_security_council_wallet
+                .or(existing_config.governance.security_council_wallet),
         },
         bridgehub: BridgehubConfig {
+            address: opts.bridgehub_address.or(existing_config.bridgehub.address),
             admin_private_key: opts
                 .bridgehub_admin
                 .or(existing_config.bridgehub.admin_private_key),
             owner_private_key: opts
                 .bridgehub_owner
                 .or(existing_config.bridgehub.owner_private_key),
+            admin_safe: opts
+                .bridgehub_admin_safe
+                .or(existing_config.bridgehub.admin_safe),
+            owner_safe: opts
+                .bridgehub_owner_safe
+                .or(existing_config.bridgehub.owner_safe),
         },
         db: existing_config.db.map(|existing_db_config| DatabaseConfig {
             server: opts.server_db_url.unwrap_or(existing_db_config.server),
             prover: opts.prover_db_url.unwrap_or(existing_db_config.prover),
         }),
+        wallets: existing_config.wallets,
+        default_wallet: existing_config.default_wallet,
+        selected_wallet: None,
+        stuck_jobs: existing_config.stuck_jobs,
     };
     Ok(config)
 }
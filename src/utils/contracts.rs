@@ -1,36 +1,115 @@
 use crate::{
     config::ZKSyncConfig,
-    utils::{try_l1_signer_from_config, try_l2_provider_from_config},
+    utils::{signer::ConfigSigner, try_l1_signer_from_config, try_l2_provider_from_config},
 };
-use eyre::ContextCompat;
 use std::sync::Arc;
 use zksync_ethers_rs::{
     contracts::{bridgehub::Bridgehub, governance::Governance},
     middleware::SignerMiddleware,
     providers::Middleware,
-    signers::Signer,
+    signers::{LocalWallet, Signer},
+    types::Address,
     ZKMiddleware,
 };
 
+/// A wallet selected with `--wallet` takes precedence over the role's key in the config,
+/// which takes precedence over the `default_wallet`.
+fn role_wallet(
+    cfg: &ZKSyncConfig,
+    role: &str,
+    role_private_key: Option<&str>,
+) -> eyre::Result<ConfigSigner> {
+    match (&cfg.selected_wallet, role_private_key, &cfg.default_wallet) {
+        (Some(_), _, _) | (None, None, Some(_)) => cfg.wallet_config()?.config_signer(),
+        (None, Some(role_private_key), _) => Ok(ConfigSigner::Local(role_private_key.parse()?)),
+        (None, None, None) => {
+            eyre::bail!("{role} private key not found in config, set it or use --wallet")
+        }
+    }
+}
+
+/// The key given on the command line, or else the wallet selected with `--wallet`, the
+/// `default_wallet` or the unnamed `[wallet]`, for the roles without a key in the config, like
+/// pending owners.
+pub(crate) fn pending_role_wallet(
+    cfg: &ZKSyncConfig,
+    role: &str,
+    private_key: Option<LocalWallet>,
+) -> eyre::Result<ConfigSigner> {
+    match (
+        private_key,
+        &cfg.selected_wallet,
+        &cfg.default_wallet,
+        &cfg.wallet,
+    ) {
+        (Some(private_key), _, _, _) => Ok(ConfigSigner::Local(private_key)),
+        (None, None, None, None) => {
+            eyre::bail!("Give the {role} private key or select its wallet with --wallet")
+        }
+        (None, _, _, _) => cfg.wallet_config()?.config_signer(),
+    }
+}
+
 pub(crate) async fn try_governance_from_config(
     cfg: &ZKSyncConfig,
 ) -> eyre::Result<Governance<SignerMiddleware<impl Middleware, impl Signer>>> {
-    let l1_signer =
-        try_l1_signer_from_config(cfg.governance.owner_private_key.parse()?, cfg).await?;
+    let governance_owner = role_wallet(
+        cfg,
+        "Governance owner",
+        Some(&cfg.governance.owner_private_key),
+    )?;
+    let l1_signer = try_l1_signer_from_config(governance_owner, cfg).await?;
     Ok(Governance::new(cfg.governance.address, Arc::new(l1_signer)))
 }
 
+/// The Governance contract with the security council as signer, to execute operations
+/// instantly.
+pub(crate) async fn try_governance_security_council_from_config(
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<Governance<SignerMiddleware<impl Middleware, impl Signer>>> {
+    let governance = &cfg.governance;
+    let security_council = match (
+        &cfg.selected_wallet,
+        &governance.security_council_private_key,
+        &governance.security_council_wallet,
+    ) {
+        (None, None, Some(alias)) => cfg
+            .wallets
+            .get(alias)
+            .ok_or_else(|| eyre::eyre!("Security council wallet {alias} not found in config"))?
+            .config_signer()?,
+        _ => role_wallet(
+            cfg,
+            "Security council",
+            governance.security_council_private_key.as_deref(),
+        )?,
+    };
+    let l1_signer = try_l1_signer_from_config(security_council, cfg).await?;
+    Ok(Governance::new(governance.address, Arc::new(l1_signer)))
+}
+
 pub(crate) async fn try_bridgehub_from_config(
     cfg: &ZKSyncConfig,
 ) -> eyre::Result<Bridgehub<SignerMiddleware<impl Middleware, impl Signer>>> {
-    let bridgehub_owner = cfg
-        .bridgehub
-        .owner_private_key
-        .as_deref()
-        .context("Bridgehub owner private key not found in config")?;
-    let l1_signer = try_l1_signer_from_config(bridgehub_owner.parse()?, cfg).await?;
-    let bridgehub_address = try_l2_provider_from_config(cfg)?
-        .get_bridgehub_contract()
-        .await?;
-    Ok(Bridgehub::new(bridgehub_address, Arc::new(l1_signer)))
+    let bridgehub_owner = role_wallet(
+        cfg,
+        "Bridgehub owner",
+        cfg.bridgehub.owner_private_key.as_deref(),
+    )?;
+    let l1_signer = try_l1_signer_from_config(bridgehub_owner, cfg).await?;
+    Ok(Bridgehub::new(
+        bridgehub_address(cfg).await?,
+        Arc::new(l1_signer),
+    ))
+}
+
+/// The configured Bridgehub address, so the transactions can be signed offline, or else the
+/// one of the L2 node.
+pub(crate) async fn bridgehub_address(cfg: &ZKSyncConfig) -> eyre::Result<Address> {
+    match cfg.bridgehub.address {
+        Some(bridgehub_address) => Ok(bridgehub_address),
+        None => Ok(try_l2_provider_from_config(cfg)?
+            .get_bridgehub_contract()
+            .await?),
+    }
 }
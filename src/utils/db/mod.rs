@@ -2,5 +2,3 @@ pub(crate) mod for_batch_queries;
 pub(crate) mod prover;
 pub(crate) mod queries;
 pub(crate) mod types;
-
-pub const CURRENT_MAX_ATTEMPTS: i32 = 10;
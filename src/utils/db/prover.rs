@@ -1,8 +1,13 @@
-use crate::utils::db::{
-    queries::{get_batch_proofs_stuck_at_prover_in_agg_round, get_batch_proofs_stuck_at_wg_round},
-    types::{
-        BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
-        RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo,
+use crate::{
+    config::StuckJobsPolicy,
+    utils::db::{
+        queries::{
+            get_batch_proofs_stuck_at_prover_in_agg_round, get_batch_proofs_stuck_at_wg_round,
+        },
+        types::{
+            BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
+            RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo, StuckJob,
+        },
     },
 };
 use itertools::Itertools;
@@ -13,10 +18,11 @@ use zksync_ethers_rs::types::zksync::{basic_fri_types::AggregationRound, L1Batch
 pub async fn find_map_stuck_wg_jobs_in_aggregation_round<WG>(
     aggregation_round: AggregationRound,
     map: impl Fn(Vec<WG>) -> Vec<L1BatchNumber>,
+    policy: &StuckJobsPolicy,
     prover_db: &mut PoolConnection<Postgres>,
 ) -> eyre::Result<()>
 where
-    WG: for<'row> FromRow<'row, PgRow>,
+    WG: for<'row> FromRow<'row, PgRow> + StuckJob,
 {
     let mut spinner = Spinner::new(
         Dots,
@@ -24,7 +30,7 @@ where
         Color::Blue,
     );
     let stuck_jobs: Vec<WG> =
-        get_batch_proofs_stuck_at_wg_round(aggregation_round, prover_db).await?;
+        get_batch_proofs_stuck_at_wg_round(aggregation_round, policy, prover_db).await?;
     if !stuck_jobs.is_empty() {
         spinner.fail(&format!(
             "Stuck witness generator jobs found in {aggregation_round}: {:?}",
@@ -70,6 +76,7 @@ pub fn map_scheduler_wg_info(a: Vec<SchedulerWitnessGeneratorJobInfo>) -> Vec<L1
 
 pub async fn find_stuck_prover_jobs_in_aggregation_round(
     aggregation_round: AggregationRound,
+    policy: &StuckJobsPolicy,
     prover_db: &mut PoolConnection<Postgres>,
 ) -> eyre::Result<()> {
     let mut spinner = Spinner::new(
@@ -78,8 +85,7 @@ pub async fn find_stuck_prover_jobs_in_aggregation_round(
         Color::Blue,
     );
     let stuck_prover_jobs =
-        get_batch_proofs_stuck_at_prover_in_agg_round(prover_db, AggregationRound::NodeAggregation)
-            .await?;
+        get_batch_proofs_stuck_at_prover_in_agg_round(prover_db, aggregation_round, policy).await?;
     if !stuck_prover_jobs.is_empty() {
         let stuck_batch_proofs_in_prover = stuck_prover_jobs
             .iter()
@@ -1,4 +1,7 @@
-use crate::utils::db::{types::ProverJobFriInfo, CURRENT_MAX_ATTEMPTS};
+use crate::{
+    config::StuckJobsPolicy,
+    utils::db::types::{ProverJobFriInfo, StuckJob},
+};
 use eyre::ContextCompat;
 use sqlx::{pool::PoolConnection, postgres::PgRow, Executor, FromRow, Postgres, Row};
 use std::str::FromStr;
@@ -16,10 +19,11 @@ use super::types::proof_generation_time_from_row;
 
 pub async fn get_batch_proofs_stuck_at_wg_round<WG>(
     aggregation_round: AggregationRound,
+    policy: &StuckJobsPolicy,
     prover_db: &mut PoolConnection<Postgres>,
 ) -> eyre::Result<Vec<WG>>
 where
-    WG: for<'row> FromRow<'row, PgRow>,
+    WG: for<'row> FromRow<'row, PgRow> + StuckJob,
 {
     let table = input_table_name_for(aggregation_round);
     let query = format!(
@@ -27,42 +31,47 @@ where
         SELECT *
         FROM {table}
         WHERE
-            attempts = {CURRENT_MAX_ATTEMPTS}
-            AND status != 'success'
+            status IN ('failed', 'in_progress')
         "
     );
-    prover_db
+    let jobs = prover_db
         .fetch_all(query.as_str())
         .await?
         .iter()
         .map(WG::from_row)
-        .collect::<Result<Vec<WG>, _>>()
-        .map_err(Into::into)
+        .collect::<Result<Vec<WG>, _>>()?;
+    Ok(jobs
+        .into_iter()
+        .filter(|job| policy.is_stuck(job))
+        .collect())
 }
 
 #[allow(clippy::as_conversions, reason = "AggregationRound is an enum of u8s")]
 pub async fn get_batch_proofs_stuck_at_prover_in_agg_round(
     prover_db: &mut PoolConnection<Postgres>,
     aggregation_round: AggregationRound,
+    policy: &StuckJobsPolicy,
 ) -> eyre::Result<Vec<ProverJobFriInfo>> {
     let query = format!(
         "
         SELECT *
         FROM prover_jobs_fri
         WHERE
-            attempts = {CURRENT_MAX_ATTEMPTS}
-            AND status != 'success'
+            status IN ('failed', 'in_progress')
             AND aggregation_round = {}
         ",
         aggregation_round as u8
     );
-    prover_db
+    let jobs = prover_db
         .fetch_all(query.as_str())
         .await?
         .iter()
         .map(ProverJobFriInfo::from_row)
-        .collect::<Result<Vec<ProverJobFriInfo>, _>>()
-        .map_err(Into::into)
+        .collect::<Result<Vec<ProverJobFriInfo>, _>>()?;
+    Ok(jobs
+        .into_iter()
+        .filter(|job| policy.is_stuck(job))
+        .collect())
 }
 
 pub async fn get_compressor_job_status(
@@ -286,6 +295,86 @@ fn input_table_name_for(aggregation_round: AggregationRound) -> &'static str {
     }
 }
 
+/// Number of witness generator jobs of an aggregation round by status.
+pub async fn get_witness_jobs_count_by_status(
+    aggregation_round: AggregationRound,
+    prover_db: &mut PoolConnection<Postgres>,
+) -> eyre::Result<Vec<(String, i64)>> {
+    let table = input_table_name_for(aggregation_round);
+    let query = format!(
+        "
+        SELECT status, COUNT(*) AS count
+        FROM {table}
+        GROUP BY status
+        "
+    );
+    prover_db
+        .fetch_all(query.as_str())
+        .await?
+        .iter()
+        .map(|row| -> eyre::Result<(String, i64)> {
+            Ok((row.try_get("status")?, row.try_get("count")?))
+        })
+        .collect()
+}
+
+/// Number of prover jobs by aggregation round and status.
+pub async fn get_prover_jobs_count_by_status(
+    prover_db: &mut PoolConnection<Postgres>,
+) -> eyre::Result<Vec<(AggregationRound, String, i64)>> {
+    let query = "
+        SELECT aggregation_round, status, COUNT(*) AS count
+        FROM prover_jobs_fri
+        GROUP BY aggregation_round, status
+        ";
+    prover_db
+        .fetch_all(query)
+        .await?
+        .iter()
+        .map(|row| -> eyre::Result<(AggregationRound, String, i64)> {
+            let aggregation_round = u8::try_from(row.try_get::<i16, _>("aggregation_round")?)?;
+            Ok((
+                AggregationRound::from(aggregation_round),
+                row.try_get("status")?,
+                row.try_get("count")?,
+            ))
+        })
+        .collect()
+}
+
+/// The proof time in seconds, from witness inputs creation to proof compression, of the
+/// batches proved within the last day.
+/// Unlike `ProofGenerationTime::time_taken`, the proof time doesn't wrap after 24 hours.
+pub async fn get_recent_batch_proof_times(
+    prover_db: &mut PoolConnection<Postgres>,
+) -> eyre::Result<Vec<(L1BatchNumber, u32)>> {
+    let query = "
+        SELECT
+            comp.l1_batch_number,
+            CAST(EXTRACT(EPOCH FROM (comp.updated_at - wit.created_at)) AS BIGINT) AS proof_time_secs
+        FROM
+            proof_compression_jobs_fri AS comp
+            JOIN witness_inputs_fri AS wit ON comp.l1_batch_number = wit.l1_batch_number
+        WHERE
+            comp.status IN ('successful', 'sent_to_server')
+            AND comp.updated_at > (NOW() - INTERVAL '1 days')
+        ORDER BY
+            comp.l1_batch_number;
+        ";
+    prover_db
+        .fetch_all(query)
+        .await?
+        .iter()
+        .map(|row| -> eyre::Result<(L1BatchNumber, u32)> {
+            let l1_batch_number = u32::try_from(row.try_get::<i64, _>("l1_batch_number")?)?;
+            Ok((
+                L1BatchNumber::from(l1_batch_number),
+                u32::try_from(row.try_get::<i64, _>("proof_time_secs")?)?,
+            ))
+        })
+        .collect()
+}
+
 pub async fn get_proof_time_within_period(
     prover_db: &mut PoolConnection<Postgres>,
     days: u32,
@@ -1,4 +1,5 @@
 use chrono::{NaiveDateTime, NaiveTime};
+use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
 use sqlx::{postgres::PgRow, FromRow, Row};
 use std::{num::TryFromIntError, str::FromStr};
 use zksync_ethers_rs::types::{
@@ -69,6 +70,61 @@ pub(crate) fn combine_flags(
     flags
 }
 
+/// What `StuckJobsPolicy` needs to know about a job to tell whether it is stuck.
+pub trait StuckJob {
+    fn is_failed(&self) -> bool;
+    fn is_in_progress(&self) -> bool;
+    fn attempts(&self) -> u32;
+    fn processing_started_at(&self) -> Option<NaiveDateTime>;
+    /// `None` for the compressor.
+    fn aggregation_round(&self) -> Option<AggregationRound>;
+    fn base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+        None
+    }
+}
+
+/// Implements `StuckJob` for a job info with `_status`, `_attempts` and
+/// `_processing_started_at` fields. The jobs keyed by a circuit pass `base_layer_circuit_type`
+/// to use their inherent `base_layer_circuit_type` method.
+macro_rules! impl_stuck_job {
+    (
+        $job_info:ty,
+        failed: $failed:pat,
+        in_progress: $in_progress:pat,
+        aggregation_round: |$job:ident| $aggregation_round:expr
+        $(, $base_layer_circuit_type:ident)?
+    ) => {
+        impl StuckJob for $job_info {
+            fn is_failed(&self) -> bool {
+                matches!(self._status, $failed)
+            }
+
+            fn is_in_progress(&self) -> bool {
+                matches!(self._status, $in_progress)
+            }
+
+            fn attempts(&self) -> u32 {
+                self._attempts
+            }
+
+            fn processing_started_at(&self) -> Option<NaiveDateTime> {
+                self._processing_started_at
+            }
+
+            fn aggregation_round(&self) -> Option<AggregationRound> {
+                let $job = self;
+                $aggregation_round
+            }
+
+            $(
+                fn $base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+                    <$job_info>::$base_layer_circuit_type(self)
+                }
+            )?
+        }
+    };
+}
+
 #[derive(Debug, Clone)]
 pub struct BasicWitnessGeneratorJobInfo {
     pub l1_batch_number: L1BatchNumber,
@@ -119,6 +175,13 @@ impl Stallable for BasicWitnessGeneratorJobInfo {
     }
 }
 
+impl_stuck_job!(
+    BasicWitnessGeneratorJobInfo,
+    failed: WitnessJobStatus::Failed(_),
+    in_progress: WitnessJobStatus::InProgress,
+    aggregation_round: |_job| Some(AggregationRound::BasicCircuits)
+);
+
 #[derive(Debug, Clone)]
 pub struct ProverJobFriInfo {
     pub _id: u32,
@@ -138,6 +201,8 @@ pub struct ProverJobFriInfo {
     pub _is_node_final_proof: bool,
     pub _proof_blob_url: Option<String>,
     pub _protocol_version: Option<ProtocolVersionId>,
+    /// `_protocol_version` is `None` for the versions `ProtocolVersionId` doesn't know yet.
+    pub _raw_protocol_version: Option<i32>,
     pub _picked_by: Option<String>,
     pub _protocol_version_patch: Option<VersionPatch>,
 }
@@ -151,11 +216,10 @@ impl FromRow<'_, PgRow> for ProverJobFriInfo {
                 .map_err(|e: TryFromIntError| sqlx::Error::Decode(e.into()))?;
             AggregationRound::from(raw_aggregation_round)
         };
-        let circuit_id = get_int2_as_u32_from_pg_row(row, "circuit_id")?;
         Ok(Self {
             _id: get_id_from_pg_row(row)?,
             l1_batch_number: get_l1_batch_number_from_pg_row(row)?,
-            _circuit_id: sub2_from_circuit_id(aggregation_round, circuit_id),
+            _circuit_id: get_int2_as_u32_from_pg_row(row, "circuit_id")?,
             _circuit_blob_url: row.get("circuit_blob_url"),
             _aggregation_round: aggregation_round,
             _sequence_number: get_int4_as_u32_from_pg_row(row, "sequence_number")?,
@@ -178,36 +242,81 @@ impl FromRow<'_, PgRow> for ProverJobFriInfo {
                     .map_err(|e| sqlx::Error::Decode(e.into()))
                     .ok()
             },
+            _raw_protocol_version: row.get("protocol_version"),
             _picked_by: row.get("picked_by"),
             _protocol_version_patch: get_version_patch_from_pg_row(row).ok(),
         })
     }
 }
 
-// TODO: Old prover versions panic when using BaseLayerCircuitType::from_numeric_value
-// The quick solution is to subtract 2 to the circuit ID if the AggregationRound is greater than 2.
-// It should be fixed in the newest version
-fn sub2_from_circuit_id(aggregation_round: AggregationRound, circuit_id: u32) -> u32 {
-    match aggregation_round {
-        AggregationRound::NodeAggregation
-        | AggregationRound::RecursionTip
-        | AggregationRound::Scheduler => {
-            if circuit_id == 18 {
-                255
-            } else {
-                circuit_id.saturating_sub(2)
+impl ProverJobFriInfo {
+    /// The numeric value of the `BaseLayerCircuitType` this job proves or aggregates.
+    /// Recursion tip and scheduler jobs don't belong to a single base layer circuit.
+    pub fn base_layer_circuit_id(&self) -> Option<u8> {
+        let protocol_version = self
+            ._raw_protocol_version
+            .and_then(|v| u16::try_from(v).ok());
+        match self._aggregation_round {
+            AggregationRound::BasicCircuits => {
+                base_layer_circuit_id(self._circuit_id, protocol_version)
+            }
+            AggregationRound::LeafAggregation | AggregationRound::NodeAggregation => {
+                base_layer_circuit_id_from_recursive(self._circuit_id, protocol_version)
             }
+            AggregationRound::RecursionTip | AggregationRound::Scheduler => None,
         }
-        _ => circuit_id,
+    }
+
+    pub fn base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+        self.base_layer_circuit_id()
+            .map(BaseLayerCircuitType::from_numeric_value)
     }
 }
 
-fn get_and_sub2_from_circuit_id(row: &PgRow) -> Result<u32, sqlx::Error> {
-    let circuit_id = get_int2_as_u32_from_pg_row(row, "circuit_id")?;
-    if circuit_id == 18 {
-        return Ok(255);
+impl_stuck_job!(
+    ProverJobFriInfo,
+    failed: ProverJobStatus::Failed(_),
+    in_progress: ProverJobStatus::InProgress(_),
+    aggregation_round: |job| Some(job._aggregation_round),
+    base_layer_circuit_type
+);
+
+// The first protocol version proven with the 1.5.0 circuits, which added the
+// TransientStorageChecker and Secp256r1Verify base layer circuits.
+const PROTOCOL_VERSION_1_5_0: u16 = 24;
+const EIP4844_REPACK_CIRCUIT_ID: u8 = 255;
+
+fn last_base_layer_circuit_id(protocol_version: Option<u16>) -> u8 {
+    match protocol_version {
+        Some(protocol_version) if protocol_version < PROTOCOL_VERSION_1_5_0 => 13,
+        // Assume the latest circuits if the protocol version is unknown.
+        _ => 15,
     }
-    Ok(circuit_id.saturating_sub(2))
+}
+
+/// Returns the circuit id if it is a valid `BaseLayerCircuitType` for the given protocol
+/// version, `BaseLayerCircuitType::from_numeric_value` panics otherwise.
+pub(crate) fn base_layer_circuit_id(circuit_id: u32, protocol_version: Option<u16>) -> Option<u8> {
+    let circuit_id = u8::try_from(circuit_id).ok()?;
+    let is_valid = circuit_id == EIP4844_REPACK_CIRCUIT_ID
+        || (1..=last_base_layer_circuit_id(protocol_version)).contains(&circuit_id);
+    is_valid.then_some(circuit_id)
+}
+
+/// Translates a recursion layer (leaf or node) circuit id into the id of the base layer
+/// circuit it aggregates. Recursion layer ids are the base layer ones shifted by two (the
+/// scheduler and node circuits take 1 and 2), and EIP4844Repack takes the id right after
+/// the last leaf circuit, which depends on the protocol version.
+pub(crate) fn base_layer_circuit_id_from_recursive(
+    circuit_id: u32,
+    protocol_version: Option<u16>,
+) -> Option<u8> {
+    let eip4844_repack_leaf_circuit_id =
+        u32::from(last_base_layer_circuit_id(protocol_version)) + 3;
+    if circuit_id == eip4844_repack_leaf_circuit_id {
+        return Some(EIP4844_REPACK_CIRCUIT_ID);
+    }
+    base_layer_circuit_id(circuit_id.checked_sub(2)?, protocol_version)
 }
 
 #[derive(Debug, Clone)]
@@ -239,6 +348,23 @@ impl Stallable for LeafWitnessGeneratorJobInfo {
     }
 }
 
+impl_stuck_job!(
+    LeafWitnessGeneratorJobInfo,
+    failed: WitnessJobStatus::Failed(_),
+    in_progress: WitnessJobStatus::InProgress,
+    aggregation_round: |_job| Some(AggregationRound::LeafAggregation),
+    base_layer_circuit_type
+);
+
+impl LeafWitnessGeneratorJobInfo {
+    /// Leaf aggregation jobs are keyed by the base layer circuit they aggregate.
+    pub fn base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+        let protocol_version = self._protocol_version.and_then(|v| u16::try_from(v).ok());
+        base_layer_circuit_id(self._circuit_id, protocol_version)
+            .map(BaseLayerCircuitType::from_numeric_value)
+    }
+}
+
 impl FromRow<'_, PgRow> for LeafWitnessGeneratorJobInfo {
     fn from_row(row: &'_ PgRow) -> Result<Self, sqlx::Error> {
         Ok(Self {
@@ -291,12 +417,29 @@ impl Stallable for NodeWitnessGeneratorJobInfo {
     }
 }
 
+impl_stuck_job!(
+    NodeWitnessGeneratorJobInfo,
+    failed: WitnessJobStatus::Failed(_),
+    in_progress: WitnessJobStatus::InProgress,
+    aggregation_round: |_job| Some(AggregationRound::NodeAggregation),
+    base_layer_circuit_type
+);
+
+impl NodeWitnessGeneratorJobInfo {
+    /// Node aggregation jobs are keyed by the recursion layer circuit they aggregate.
+    pub fn base_layer_circuit_type(&self) -> Option<BaseLayerCircuitType> {
+        let protocol_version = self._protocol_version.and_then(|v| u16::try_from(v).ok());
+        base_layer_circuit_id_from_recursive(self._circuit_id, protocol_version)
+            .map(BaseLayerCircuitType::from_numeric_value)
+    }
+}
+
 impl FromRow<'_, PgRow> for NodeWitnessGeneratorJobInfo {
     fn from_row(row: &'_ PgRow) -> Result<Self, sqlx::Error> {
         Ok(Self {
             _id: get_id_from_pg_row(row)?,
             l1_batch_number: get_l1_batch_number_from_pg_row(row)?,
-            _circuit_id: get_and_sub2_from_circuit_id(row)?,
+            _circuit_id: get_int2_as_u32_from_pg_row(row, "circuit_id")?,
             _depth: get_depth_from_pg_row(row)?,
             _status: get_witness_job_status_from_pg_row(row)?,
             _attempts: get_int2_as_u32_from_pg_row(row, "attempts")?,
@@ -340,6 +483,13 @@ impl Stallable for RecursionTipWitnessGeneratorJobInfo {
     }
 }
 
+impl_stuck_job!(
+    RecursionTipWitnessGeneratorJobInfo,
+    failed: WitnessJobStatus::Failed(_),
+    in_progress: WitnessJobStatus::InProgress,
+    aggregation_round: |_job| Some(AggregationRound::RecursionTip)
+);
+
 impl FromRow<'_, PgRow> for RecursionTipWitnessGeneratorJobInfo {
     fn from_row(row: &'_ PgRow) -> Result<Self, sqlx::Error> {
         Ok(Self {
@@ -385,6 +535,13 @@ impl Stallable for SchedulerWitnessGeneratorJobInfo {
     }
 }
 
+impl_stuck_job!(
+    SchedulerWitnessGeneratorJobInfo,
+    failed: WitnessJobStatus::Failed(_),
+    in_progress: WitnessJobStatus::InProgress,
+    aggregation_round: |_job| Some(AggregationRound::Scheduler)
+);
+
 impl FromRow<'_, PgRow> for SchedulerWitnessGeneratorJobInfo {
     fn from_row(row: &'_ PgRow) -> Result<Self, sqlx::Error> {
         Ok(Self {
@@ -437,6 +594,13 @@ impl FromRow<'_, PgRow> for ProofCompressionJobInfo {
     }
 }
 
+impl_stuck_job!(
+    ProofCompressionJobInfo,
+    failed: ProofCompressionJobStatus::Failed,
+    in_progress: ProofCompressionJobStatus::InProgress,
+    aggregation_round: |_job| None
+);
+
 pub(crate) fn proof_generation_time_from_row(
     row: &'_ PgRow,
 ) -> Result<ProofGenerationTime, sqlx::Error> {
@@ -496,3 +660,49 @@ fn get_proof_compression_job_status_from_pg_row(
     let raw_status = row.get::<&str, &str>("status");
     ProofCompressionJobStatus::from_str(raw_status).map_err(|e| sqlx::Error::Decode(e.into()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{base_layer_circuit_id, base_layer_circuit_id_from_recursive};
+
+    #[test]
+    fn base_layer_circuit_ids_depend_on_the_protocol_version() {
+        assert_eq!(base_layer_circuit_id(0, Some(23)), None);
+        assert_eq!(base_layer_circuit_id(1, Some(23)), Some(1));
+        assert_eq!(base_layer_circuit_id(13, Some(23)), Some(13));
+        assert_eq!(base_layer_circuit_id(14, Some(23)), None);
+        assert_eq!(base_layer_circuit_id(255, Some(23)), Some(255));
+        assert_eq!(base_layer_circuit_id(14, Some(24)), Some(14));
+        assert_eq!(base_layer_circuit_id(15, Some(24)), Some(15));
+        assert_eq!(base_layer_circuit_id(16, Some(24)), None);
+        assert_eq!(base_layer_circuit_id(255, Some(24)), Some(255));
+        assert_eq!(base_layer_circuit_id(15, None), Some(15));
+        assert_eq!(base_layer_circuit_id(256, None), None);
+    }
+
+    #[test]
+    fn recursive_circuit_ids_before_1_5_0() {
+        assert_eq!(base_layer_circuit_id_from_recursive(1, Some(23)), None);
+        assert_eq!(base_layer_circuit_id_from_recursive(2, Some(23)), None);
+        assert_eq!(base_layer_circuit_id_from_recursive(3, Some(23)), Some(1));
+        assert_eq!(base_layer_circuit_id_from_recursive(15, Some(23)), Some(13));
+        assert_eq!(
+            base_layer_circuit_id_from_recursive(16, Some(23)),
+            Some(255)
+        );
+        assert_eq!(base_layer_circuit_id_from_recursive(17, Some(23)), None);
+    }
+
+    #[test]
+    fn recursive_circuit_ids_from_1_5_0() {
+        assert_eq!(base_layer_circuit_id_from_recursive(3, Some(24)), Some(1));
+        assert_eq!(base_layer_circuit_id_from_recursive(16, Some(24)), Some(14));
+        assert_eq!(base_layer_circuit_id_from_recursive(17, Some(24)), Some(15));
+        assert_eq!(
+            base_layer_circuit_id_from_recursive(18, Some(24)),
+            Some(255)
+        );
+        assert_eq!(base_layer_circuit_id_from_recursive(19, Some(24)), None);
+        assert_eq!(base_layer_circuit_id_from_recursive(18, None), Some(255));
+    }
+}
@@ -0,0 +1,82 @@
+use spinoff::{spinners, Color, Spinner};
+use std::time::{Duration, Instant};
+use zksync_ethers_rs::{
+    deposit::l2_deposit_tx_hash,
+    providers::{Http, Middleware, Provider},
+    types::{TransactionReceipt, H256},
+};
+
+const DEPOSIT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+pub(crate) const DEFAULT_DEPOSIT_TIMEOUT_SECS: u64 = 1800;
+
+/// Follows an L1 deposit until its priority operation is executed on L2, returning the L2
+/// receipt. Fails if it takes longer than `timeout`.
+pub(crate) async fn wait_for_l2_deposit(
+    l1_deposit_tx_hash: H256,
+    timeout: Duration,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<TransactionReceipt> {
+    let deadline = Instant::now() + timeout;
+    let mut spinner = Spinner::new(
+        spinners::Dots,
+        "Waiting for the L1 deposit transaction",
+        Color::Blue,
+    );
+    let l1_receipt = loop {
+        if let Some(receipt) = l1_provider
+            .get_transaction_receipt(l1_deposit_tx_hash)
+            .await?
+        {
+            break receipt;
+        }
+        if Instant::now() >= deadline {
+            spinner.fail("Timed out waiting for the L1 deposit transaction");
+            eyre::bail!(
+                "L1 deposit transaction {l1_deposit_tx_hash:?} not mined after {}s",
+                timeout.as_secs()
+            );
+        }
+        tokio::time::sleep(DEPOSIT_POLL_INTERVAL).await;
+    };
+    if l1_receipt.status.is_some_and(|status| status.is_zero()) {
+        spinner.fail("L1 deposit transaction reverted");
+        eyre::bail!("L1 deposit transaction {l1_deposit_tx_hash:?} reverted");
+    }
+
+    // The L2 transaction hash is the priority operation hash emitted on L1.
+    let l2_tx_hash = l2_deposit_tx_hash(l1_deposit_tx_hash, l1_provider).await;
+    spinner.update(
+        spinners::Dots,
+        format!("Waiting for the L2 deposit transaction {l2_tx_hash:?}"),
+        Color::Blue,
+    );
+    let l2_receipt = loop {
+        if let Some(receipt) = l2_provider.get_transaction_receipt(l2_tx_hash).await? {
+            break receipt;
+        }
+        if Instant::now() >= deadline {
+            spinner.fail("Timed out waiting for the L2 deposit transaction");
+            eyre::bail!(
+                "L2 deposit transaction {l2_tx_hash:?} not executed after {}s, follow it again with `zks wallet deposit-status {l1_deposit_tx_hash:?}`",
+                timeout.as_secs()
+            );
+        }
+        tokio::time::sleep(DEPOSIT_POLL_INTERVAL).await;
+    };
+    spinner.success("Deposit processed on L2");
+    Ok(l2_receipt)
+}
+
+/// Prints whether the deposit succeeded on L2, with a hint to recover the funds if it failed.
+pub(crate) fn display_l2_deposit_result(l2_receipt: &TransactionReceipt, l2_explorer_url: &str) {
+    let l2_tx_hash = l2_receipt.transaction_hash;
+    if l2_receipt.status.is_some_and(|status| status.is_zero()) {
+        println!("Deposit failed on L2: {l2_explorer_url}/tx/{l2_tx_hash:?}");
+        println!(
+            "The L2 transaction reverted (e.g. it ran out of gas). The deposited funds can be claimed back on L1 by calling `claimFailedDeposit` on the L1 shared bridge."
+        );
+    } else {
+        println!("Deposit executed on L2: {l2_explorer_url}/tx/{l2_tx_hash:?}");
+    }
+}
@@ -0,0 +1,372 @@
+use crate::utils::{
+    logs::get_logs_in_range,
+    upgrade::{STM_EXECUTE_UPGRADE, UPGRADE_CHAIN_FROM_VERSION},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use zksync_ethers_rs::{
+    abi::{decode, parse_abi_str, Function, ParamType, Token},
+    contracts::governance::{Call, Operation},
+    core::utils::keccak256,
+    providers::{Http, Middleware, Provider, RpcError},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes,
+        Eip1559TransactionRequest, Filter, H256, U256, U64,
+    },
+};
+
+const TRANSPARENT_OPERATION_SCHEDULED: &str =
+    "TransparentOperationScheduled(bytes32,uint256,((address,uint256,bytes)[],bytes32,bytes32))";
+const SHADOW_OPERATION_SCHEDULED: &str = "ShadowOperationScheduled(bytes32,uint256)";
+const OPERATION_EXECUTED: &str = "OperationExecuted(bytes32)";
+const OPERATION_CANCELLED: &str = "OperationCancelled(bytes32)";
+
+/// Functions the operations calls are decoded with, besides the ones given by the user.
+const KNOWN_FUNCTIONS: &[&str] = &[
+    "updateDelay(uint256)",
+    "updateSecurityCouncil(address)",
+    "setPendingAdmin(address)",
+    "acceptAdmin()",
+    "addToken(address)",
+    "addStateTransitionManager(address)",
+    "removeStateTransitionManager(address)",
+    "setSharedBridge(address)",
+    "createNewChain(uint256,address,address,uint256,address,bytes)",
+    "pause()",
+    "unpause()",
+    "transferOwnership(address)",
+    "acceptOwnership()",
+    UPGRADE_CHAIN_FROM_VERSION,
+    STM_EXECUTE_UPGRADE,
+    "executeUpgrade(((address,uint8,bool,bytes4[])[],address,bytes))",
+    "setNewVersionUpgrade(((address,uint8,bool,bytes4[])[],address,bytes),uint256,uint256)",
+    "setValidator(address,bool)",
+    "setPorterAvailability(bool)",
+    "setPriorityTxMaxGasLimit(uint256)",
+];
+
+/// A scheduled Governance operation and what happened to it since.
+pub struct ScheduledOperation {
+    pub id: H256,
+    /// Only transparent operations publish their calls.
+    pub operation: Option<Operation>,
+    pub delay: U256,
+    pub scheduled_at: DateTime<Utc>,
+    pub scheduled_tx_hash: Option<H256>,
+    pub executed_tx_hash: Option<H256>,
+    pub cancelled_tx_hash: Option<H256>,
+}
+
+/// Scans the operations scheduled in the Governance contract between the given blocks, in
+/// scheduling order. An operation scheduled again after being cancelled keeps only its last
+/// scheduling.
+pub(crate) async fn scan_operations(
+    governance: Address,
+    from_block: u64,
+    to_block: Option<u64>,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<Vec<ScheduledOperation>> {
+    let topics: Vec<H256> = [
+        TRANSPARENT_OPERATION_SCHEDULED,
+        SHADOW_OPERATION_SCHEDULED,
+        OPERATION_EXECUTED,
+        OPERATION_CANCELLED,
+    ]
+    .iter()
+    .map(|event| H256::from(keccak256(event)))
+    .collect();
+    let filter = Filter::new().address(governance).topic0(topics);
+    let logs = get_logs_in_range(filter, from_block, to_block, l1_provider).await?;
+
+    let mut operations: Vec<ScheduledOperation> = Vec::new();
+    let mut block_timestamps: HashMap<U64, DateTime<Utc>> = HashMap::new();
+    for log in logs {
+        let (Some(event), Some(id)) = (log.topics.first(), log.topics.get(1)) else {
+            continue;
+        };
+        let position = operations.iter().position(|operation| operation.id == *id);
+        if *event == H256::from(keccak256(OPERATION_EXECUTED)) {
+            if let Some(operation) = position.and_then(|i| operations.get_mut(i)) {
+                operation.executed_tx_hash = log.transaction_hash;
+            }
+            continue;
+        }
+        if *event == H256::from(keccak256(OPERATION_CANCELLED)) {
+            if let Some(operation) = position.and_then(|i| operations.get_mut(i)) {
+                operation.cancelled_tx_hash = log.transaction_hash;
+            }
+            continue;
+        }
+
+        let (delay, operation) = if *event == H256::from(keccak256(SHADOW_OPERATION_SCHEDULED)) {
+            let mut tokens = decode(&[ParamType::Uint(256)], &log.data)?.into_iter();
+            (tokens.next().and_then(Token::into_uint), None)
+        } else {
+            let mut tokens =
+                decode(&[ParamType::Uint(256), operation_param_type()], &log.data)?.into_iter();
+            let delay = tokens.next().and_then(Token::into_uint);
+            (delay, tokens.next().map(operation_from_token).transpose()?)
+        };
+        let block_number = log
+            .block_number
+            .ok_or_else(|| eyre::eyre!("Log of operation {id:?} without block number"))?;
+        let scheduled_at = match block_timestamps.get(&block_number) {
+            Some(timestamp) => *timestamp,
+            None => {
+                let block = l1_provider
+                    .get_block(block_number)
+                    .await?
+                    .ok_or_else(|| eyre::eyre!("Block {block_number} not found"))?;
+                let timestamp = DateTime::from_timestamp(block.timestamp.as_u64().try_into()?, 0)
+                    .unwrap_or_default();
+                block_timestamps.insert(block_number, timestamp);
+                timestamp
+            }
+        };
+        let scheduled_operation = ScheduledOperation {
+            id: *id,
+            operation,
+            delay: delay.unwrap_or_default(),
+            scheduled_at,
+            scheduled_tx_hash: log.transaction_hash,
+            executed_tx_hash: None,
+            cancelled_tx_hash: None,
+        };
+        if let Some(i) = position {
+            operations.remove(i);
+        }
+        operations.push(scheduled_operation);
+    }
+    Ok(operations)
+}
+
+fn operation_param_type() -> ParamType {
+    ParamType::Tuple(vec![
+        ParamType::Array(Box::new(ParamType::Tuple(vec![
+            ParamType::Address,
+            ParamType::Uint(256),
+            ParamType::Bytes,
+        ]))),
+        ParamType::FixedBytes(32),
+        ParamType::FixedBytes(32),
+    ])
+}
+
+fn operation_from_token(token: Token) -> eyre::Result<Operation> {
+    let invalid = || eyre::eyre!("Invalid operation in TransparentOperationScheduled event");
+    let mut fields = token.into_tuple().ok_or_else(invalid)?.into_iter();
+    let calls = fields
+        .next()
+        .and_then(Token::into_array)
+        .ok_or_else(invalid)?
+        .into_iter()
+        .map(|call| {
+            let mut call_fields = call.into_tuple().ok_or_else(invalid)?.into_iter();
+            Ok(Call {
+                target: call_fields
+                    .next()
+                    .and_then(Token::into_address)
+                    .ok_or_else(invalid)?,
+                value: call_fields
+                    .next()
+                    .and_then(Token::into_uint)
+                    .ok_or_else(invalid)?,
+                data: call_fields
+                    .next()
+                    .and_then(Token::into_bytes)
+                    .ok_or_else(invalid)?
+                    .into(),
+            })
+        })
+        .collect::<eyre::Result<Vec<Call>>>()?;
+    let mut fixed_bytes = || -> eyre::Result<[u8; 32]> {
+        let bytes = fields
+            .next()
+            .and_then(Token::into_fixed_bytes)
+            .ok_or_else(invalid)?;
+        Ok(H256::from_slice(&bytes).0)
+    };
+    Ok(Operation {
+        calls,
+        predecessor: fixed_bytes()?,
+        salt: fixed_bytes()?,
+    })
+}
+
+/// Runs a call of an operation as an `eth_call` from the Governance contract, against the
+/// latest state. Returns the revert reason if it reverts.
+/// The value of a call is sent by `execute` along with it, so if the Governance balance is
+/// lower, the call is run with the balance overridden to the call value. The L1 node must
+/// then support `eth_call` state overrides.
+pub(crate) async fn simulate_call(
+    governance: Address,
+    call: &Call,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<Option<String>> {
+    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+        .from(governance)
+        .to(call.target)
+        .value(call.value)
+        .data(call.data.clone())
+        .into();
+    let result = if l1_provider.get_balance(governance, None).await? < call.value {
+        let state_override =
+            HashMap::from([(governance, serde_json::json!({ "balance": call.value }))]);
+        l1_provider
+            .request::<_, Bytes>("eth_call", (&tx, BlockNumber::Latest, state_override))
+            .await
+    } else {
+        l1_provider.call(&tx, None).await
+    };
+    match result {
+        Ok(_) => Ok(None),
+        Err(e) => match e.as_error_response() {
+            Some(error_response) if error_response.is_revert() => Ok(Some(
+                error_response
+                    .as_revert_data()
+                    .map(|revert_data| decode_revert_reason(&revert_data))
+                    .unwrap_or_else(|| error_response.message.clone()),
+            )),
+            Some(error_response) => Ok(Some(error_response.message.clone())),
+            None => Err(e.into()),
+        },
+    }
+}
+
+/// Decodes `Error(string)` and `Panic(uint256)` reverts, custom errors are shown as raw data.
+fn decode_revert_reason(revert_data: &Bytes) -> String {
+    let decode_arg = |param_type: ParamType| {
+        revert_data
+            .get(4..)
+            .and_then(|data| decode(&[param_type], data).ok())
+            .and_then(|mut tokens| tokens.pop())
+    };
+    match revert_data.get(..4) {
+        None => "reverted without reason".to_owned(),
+        Some([0x08, 0xc3, 0x79, 0xa0]) => decode_arg(ParamType::String)
+            .and_then(Token::into_string)
+            .unwrap_or_else(|| format!("{revert_data}")),
+        Some([0x4e, 0x48, 0x7b, 0x71]) => decode_arg(ParamType::Uint(256))
+            .and_then(Token::into_uint)
+            .map(|code| format!("panic {code:#x}"))
+            .unwrap_or_else(|| format!("{revert_data}")),
+        Some(_) => format!("custom error {revert_data}"),
+    }
+}
+
+/// Decodes the calldata of a call with the known functions and the `extra_functions`
+/// signatures, e.g. `setPendingAdmin(0x…)`. Unknown calls are shown as raw calldata.
+pub(crate) fn decode_call(data: &[u8], extra_functions: &[String]) -> String {
+    let raw_calldata = || format!("0x{}", hex::encode(data));
+    let Some(selector) = data.get(..4) else {
+        return raw_calldata();
+    };
+    let function = KNOWN_FUNCTIONS
+        .iter()
+        .copied()
+        .chain(extra_functions.iter().map(String::as_str))
+        .filter_map(|signature| parse_function(signature).ok())
+        .find(|function| function.short_signature() == selector);
+    let Some(function) = function else {
+        return raw_calldata();
+    };
+    match function.decode_input(data.get(4..).unwrap_or_default()) {
+        Ok(args) => format!(
+            "{}({})",
+            function.name,
+            args.iter().map(format_token).collect::<Vec<_>>().join(", ")
+        ),
+        Err(_) => raw_calldata(),
+    }
+}
+
+fn parse_function(signature: &str) -> eyre::Result<Function> {
+    parse_abi_str(signature)?
+        .functions()
+        .next()
+        .cloned()
+        .ok_or_else(|| eyre::eyre!("No function in {signature}"))
+}
+
+fn format_token(token: &Token) -> String {
+    match token {
+        Token::Address(address) => format!("{address:?}"),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Int(int) => format!("{}", zksync_ethers_rs::types::I256::from_raw(*int)),
+        Token::Uint(uint) => format!("{uint}"),
+        Token::Bool(boolean) => format!("{boolean}"),
+        Token::String(string) => format!("{string:?}"),
+        Token::Array(tokens) | Token::FixedArray(tokens) => format!(
+            "[{}]",
+            tokens
+                .iter()
+                .map(format_token)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Token::Tuple(tokens) => format!(
+            "({})",
+            tokens
+                .iter()
+                .map(format_token)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Formats a number of seconds as e.g. `1d 2h 3m 4s`.
+pub(crate) fn format_duration(seconds: u64) -> String {
+    let units = [("d", 86_400_u64), ("h", 3_600_u64), ("m", 60_u64)];
+    let mut remaining = seconds;
+    let mut parts = Vec::new();
+    for (unit, unit_seconds) in units {
+        if remaining >= unit_seconds {
+            parts.push(format!("{}{unit}", remaining / unit_seconds));
+            remaining %= unit_seconds;
+        }
+    }
+    if remaining > 0 || parts.is_empty() {
+        parts.push(format!("{remaining}s"));
+    }
+    parts.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_call;
+    use crate::utils::upgrade::{DiamondCutData, FacetAction, FacetCut};
+    use zksync_ethers_rs::types::{Address, U256};
+
+    fn diamond_cut() -> DiamondCutData {
+        DiamondCutData {
+            facet_cuts: vec![FacetCut {
+                facet: Address::from_low_u64_be(1),
+                action: FacetAction::Replace,
+                is_freezable: false,
+                selectors: vec![[0x12, 0x34, 0x56, 0x78]],
+            }],
+            init_address: Address::zero(),
+            init_calldata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn decode_built_upgrade_calls() {
+        let decoded_cut = "([(0x0000000000000000000000000000000000000001, 1, false, [0x12345678])], 0x0000000000000000000000000000000000000000, 0x)";
+        assert_eq!(
+            decode_call(
+                &diamond_cut().stm_execute_upgrade_calldata(U256::from(270_u64)),
+                &[]
+            ),
+            format!("executeUpgrade(270, {decoded_cut})")
+        );
+        assert_eq!(
+            decode_call(
+                &diamond_cut().upgrade_chain_from_version_calldata(U256::from(24_u64)),
+                &[]
+            ),
+            format!("upgradeChainFromVersion(24, {decoded_cut})")
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::utils::config::zks_dir_path;
+use dialoguer::{theme::ColorfulTheme, Password};
+use std::{collections::BTreeMap, path::PathBuf, sync::Mutex};
+use zksync_ethers_rs::{core::rand::thread_rng, signers::LocalWallet, types::Address};
+
+pub const KEYSTORE_PASSWORD_ENV_VAR: &str = "ZKS_KEYSTORE_PASSWORD";
+
+/// Keystores already decrypted by this run, so that their password is only asked once.
+static DECRYPTED_KEYSTORES: Mutex<BTreeMap<String, LocalWallet>> = Mutex::new(BTreeMap::new());
+
+pub fn keystores_dir_path() -> eyre::Result<PathBuf> {
+    zks_dir_path("keystores")
+}
+
+pub fn keystore_path(keystore_name: &str) -> eyre::Result<PathBuf> {
+    // Keystores are files of the keystores directory, their name must not point elsewhere.
+    if keystore_name.is_empty()
+        || keystore_name == "."
+        || keystore_name.contains("..")
+        || keystore_name.chars().any(std::path::is_separator)
+    {
+        eyre::bail!(
+            "Invalid keystore name {keystore_name:?}, it can't contain path separators or .."
+        );
+    }
+    Ok(keystores_dir_path()?.join(keystore_name))
+}
+
+/// Reads the keystore password from `ZKS_KEYSTORE_PASSWORD`, prompting for it if unset.
+/// New passwords are asked twice.
+pub fn keystore_password(keystore_name: &str, new: bool) -> eyre::Result<String> {
+    if let Ok(password) = std::env::var(KEYSTORE_PASSWORD_ENV_VAR) {
+        return Ok(password);
+    }
+    let theme = ColorfulTheme::default();
+    let mut password =
+        Password::with_theme(&theme).with_prompt(format!("Password for keystore {keystore_name}"));
+    if new {
+        password = password.with_confirmation("Repeat password", "Passwords don't match");
+    }
+    password.interact().map_err(Into::into)
+}
+
+pub fn new_keystore(keystore_name: &str) -> eyre::Result<LocalWallet> {
+    ensure_keystore_does_not_exist(keystore_name)?;
+    let password = keystore_password(keystore_name, true)?;
+    let (wallet, _) = LocalWallet::new_keystore(
+        keystores_dir_path()?,
+        &mut thread_rng(),
+        password,
+        Some(keystore_name),
+    )?;
+    Ok(wallet)
+}
+
+pub fn import_keystore(keystore_name: &str, wallet: &LocalWallet) -> eyre::Result<()> {
+    ensure_keystore_does_not_exist(keystore_name)?;
+    let password = keystore_password(keystore_name, true)?;
+    LocalWallet::encrypt_keystore(
+        keystores_dir_path()?,
+        &mut thread_rng(),
+        wallet.signer().to_bytes(),
+        password,
+        Some(keystore_name),
+    )?;
+    Ok(())
+}
+
+pub fn decrypt_keystore(keystore_name: &str) -> eyre::Result<LocalWallet> {
+    let mut decrypted_keystores = DECRYPTED_KEYSTORES
+        .lock()
+        .map_err(|e| eyre::eyre!("Decrypted keystores lock poisoned: {e}"))?;
+    if let Some(wallet) = decrypted_keystores.get(keystore_name) {
+        return Ok(wallet.clone());
+    }
+    let keystore_path = keystore_path(keystore_name)?;
+    if !keystore_path.exists() {
+        eyre::bail!("Keystore {keystore_name} does not exist");
+    }
+    let password = keystore_password(keystore_name, false)?;
+    let wallet = LocalWallet::decrypt_keystore(keystore_path, password)
+        .map_err(|e| eyre::eyre!("Failed to decrypt keystore {keystore_name}: {e}"))?;
+    decrypted_keystores.insert(keystore_name.to_owned(), wallet.clone());
+    Ok(wallet)
+}
+
+/// Returns the keystore names along with their address, if the keystore file stores it.
+pub fn keystores() -> eyre::Result<Vec<(String, Option<Address>)>> {
+    let mut keystores = Vec::new();
+    for entry in std::fs::read_dir(keystores_dir_path()?)? {
+        let entry = entry?;
+        let Ok(keystore_name) = entry.file_name().into_string() else {
+            continue;
+        };
+        let address = std::fs::read_to_string(entry.path())
+            .ok()
+            .and_then(|keystore| serde_json::from_str::<serde_json::Value>(&keystore).ok())
+            .and_then(|keystore| {
+                keystore
+                    .get("address")
+                    .and_then(|address| address.as_str())
+                    .and_then(|address| address.trim_start_matches("0x").parse().ok())
+            });
+        keystores.push((keystore_name, address));
+    }
+    keystores.sort();
+    Ok(keystores)
+}
+
+fn ensure_keystore_does_not_exist(keystore_name: &str) -> eyre::Result<()> {
+    if keystore_path(keystore_name)?.exists() {
+        eyre::bail!("Keystore {keystore_name} already exists");
+    }
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use zksync_ethers_rs::{
+    providers::{Http, Middleware, Provider},
+    types::{BlockNumber, Filter, Log},
+};
+
+/// Blocks queried per `eth_getLogs` request, most RPC providers limit the range.
+const LOGS_BLOCK_RANGE: u64 = 10_000;
+
+/// The logs matching `filter` between the given blocks, the latest one if no `to_block` is
+/// given, queried in ranges of `LOGS_BLOCK_RANGE` blocks.
+pub(crate) async fn get_logs_in_range(
+    filter: Filter,
+    from_block: u64,
+    to_block: Option<u64>,
+    provider: &Provider<Http>,
+) -> eyre::Result<Vec<Log>> {
+    let to_block = match to_block {
+        Some(to_block) => to_block,
+        None => provider.get_block_number().await?.as_u64(),
+    };
+    let mut logs: Vec<Log> = Vec::new();
+    let mut chunk_start = from_block;
+    while chunk_start <= to_block {
+        let chunk_end = to_block.min(chunk_start.saturating_add(LOGS_BLOCK_RANGE - 1));
+        let chunk_filter = filter
+            .clone()
+            .from_block(BlockNumber::Number(chunk_start.into()))
+            .to_block(BlockNumber::Number(chunk_end.into()));
+        logs.extend(provider.get_logs(&chunk_filter).await?);
+        chunk_start = chunk_end.saturating_add(1);
+    }
+    Ok(logs)
+}
@@ -34,3 +34,7 @@ pub const DATABASE_PROVER_RECURSION_NODE_VK_HASH_PROMPT_MSG: &str = "Recursion N
 pub const DATABASE_PROVER_RECURSION_LEAF_VK_HASH_PROMPT_MSG: &str = "Recursion Leaf Level VK Hash";
 pub const DATABASE_PROVER_RECURSION_CIRCUITS_SET_PROMPT_MSG: &str =
     "Recursion Circuits Set VKs Hash";
+
+// `zks wallet` messages
+pub const WALLET_EXPORT_PRIVATE_KEY_CONFIRMATION_MSG: &str =
+    "The private key will be printed in plain text. Are you sure you want to proceed?";
@@ -0,0 +1,176 @@
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+    Request, Response,
+};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Value};
+use std::{convert::Infallible, net::SocketAddr, sync::Arc};
+use tokio::net::TcpListener;
+use zksync_ethers_rs::{
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes as HexBytes},
+};
+
+/// Serves the `eth_accounts`, `eth_sign` and `eth_signTransaction` JSON-RPC methods of a
+/// remote signer holding `wallet`, to test remote signer wallets without a real one.
+pub(crate) async fn serve_mock_signer(wallet: LocalWallet, listen: SocketAddr) -> eyre::Result<()> {
+    let listener = TcpListener::bind(listen).await?;
+    println!(
+        "Serving a mock signer for {:?} on http://{listen}",
+        wallet.address()
+    );
+    serve_signer_connections(listener, Arc::new(wallet)).await
+}
+
+async fn serve_signer_connections(
+    listener: TcpListener,
+    wallet: Arc<LocalWallet>,
+) -> eyre::Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let wallet = Arc::clone(&wallet);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let wallet = Arc::clone(&wallet);
+                async move { serve_signer_request(request, &wallet).await }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::error!("Failed to serve mock signer connection: {e:?}");
+            }
+        });
+    }
+}
+
+async fn serve_signer_request(
+    request: Request<Incoming>,
+    wallet: &LocalWallet,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let rpc_request = match request.into_body().collect().await {
+        Ok(body) => serde_json::from_slice::<Value>(&body.to_bytes()).map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    let response = match rpc_request {
+        Ok(rpc_request) => {
+            let id = rpc_request.get("id").cloned().unwrap_or(Value::Null);
+            match handle_signer_method(&rpc_request, wallet).await {
+                Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                Err(e) => rpc_error(id, -32000_i32, &e.to_string()),
+            }
+        }
+        Err(e) => rpc_error(Value::Null, -32700_i32, &e),
+    };
+    let mut http_response = Response::new(Full::new(Bytes::from(response.to_string())));
+    http_response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static("application/json"),
+    );
+    Ok(http_response)
+}
+
+fn rpc_error(id: Value, code: i32, message: &str) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+async fn handle_signer_method(rpc_request: &Value, wallet: &LocalWallet) -> eyre::Result<Value> {
+    let method = rpc_request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let params = rpc_request.get("params").cloned().unwrap_or(Value::Null);
+    let param = |index: usize| -> eyre::Result<Value> {
+        params
+            .get(index)
+            .cloned()
+            .ok_or_else(|| eyre::eyre!("Missing {method} param {index}"))
+    };
+    match method {
+        "eth_accounts" => Ok(json!([wallet.address()])),
+        "eth_sign" => {
+            let address: Address = serde_json::from_value(param(0)?)?;
+            if address != wallet.address() {
+                eyre::bail!("Unknown account {address:?}");
+            }
+            let message: HexBytes = serde_json::from_value(param(1)?)?;
+            let signature = wallet.sign_message(message.as_ref()).await?;
+            Ok(json!(HexBytes::from(signature.to_vec())))
+        }
+        "eth_signTransaction" => {
+            let tx: TypedTransaction = serde_json::from_value(param(0)?)?;
+            if tx.from().is_some_and(|from| *from != wallet.address()) {
+                eyre::bail!("Unknown account {:?}", tx.from());
+            }
+            let signature = wallet.sign_transaction(&tx).await?;
+            Ok(json!(tx.rlp_signed(&signature)))
+        }
+        _ => eyre::bail!("Method {method} not supported by the mock signer"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::serve_signer_connections;
+    use crate::utils::signer::{ConfigSigner, RemoteSigner};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use zksync_ethers_rs::{
+        signers::{LocalWallet, Signer},
+        types::{transaction::eip2718::TypedTransaction, Address, Eip1559TransactionRequest},
+    };
+
+    const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    async fn spawn_mock_signer(wallet: &LocalWallet) -> eyre::Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(serve_signer_connections(listener, Arc::new(wallet.clone())));
+        Ok(url)
+    }
+
+    #[tokio::test]
+    async fn remote_signer_signs_like_the_local_wallet() -> eyre::Result<()> {
+        let wallet: LocalWallet = PRIVATE_KEY.parse()?;
+        let url = spawn_mock_signer(&wallet).await?;
+        let remote_signer = ConfigSigner::Remote(RemoteSigner::new(&url, wallet.address())?);
+
+        let message = b"zks mock signer";
+        assert_eq!(
+            remote_signer.sign_message(message).await?,
+            wallet.sign_message(message).await?
+        );
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .from(wallet.address())
+            .to(Address::zero())
+            .value(1_u64)
+            .nonce(0_u64)
+            .gas(21_000_u64)
+            .max_fee_per_gas(1_u64)
+            .max_priority_fee_per_gas(1_u64)
+            .chain_id(1_u64)
+            .into();
+        assert_eq!(
+            remote_signer.sign_transaction(&tx).await?,
+            wallet.sign_transaction(&tx).await?
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mock_signer_rejects_unknown_accounts() -> eyre::Result<()> {
+        let wallet: LocalWallet = PRIVATE_KEY.parse()?;
+        let url = spawn_mock_signer(&wallet).await?;
+        let remote_signer = ConfigSigner::Remote(RemoteSigner::new(&url, Address::zero())?);
+        assert!(remote_signer.sign_message(b"zks").await.is_err());
+        Ok(())
+    }
+}
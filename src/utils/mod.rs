@@ -3,20 +3,38 @@ use eyre::ContextCompat;
 use zksync_ethers_rs::{
     middleware::SignerMiddleware,
     providers::{Http, Middleware, Provider},
-    signers::{LocalWallet, Signer},
+    signers::Signer,
 };
 
+use self::signer::ConfigSigner;
+
+pub(crate) mod amount;
 pub(crate) mod balance;
 pub(crate) mod chain;
 pub(crate) mod config;
 pub(crate) mod contract;
 pub(crate) mod contracts;
 pub(crate) mod db;
+pub(crate) mod deposit;
 pub(crate) mod gas_tracker;
+pub(crate) mod governance;
+pub(crate) mod keystore;
+pub(crate) mod logs;
 pub(crate) mod messages;
+pub(crate) mod mock_signer;
+pub(crate) mod paymaster;
+pub(crate) mod prover_artifacts;
+pub(crate) mod prover_exporter;
 pub(crate) mod prover_status;
+pub(crate) mod safe;
+pub(crate) mod signed_tx;
+pub(crate) mod signer;
 pub(crate) mod test;
+pub(crate) mod test_runs;
+pub(crate) mod tx;
+pub(crate) mod upgrade;
 pub(crate) mod wallet;
+pub(crate) mod withdrawals;
 
 pub(crate) fn try_l2_provider_from_config(cfg: &ZKSyncConfig) -> eyre::Result<Provider<Http>> {
     Provider::try_from(cfg.network.l2_rpc_url.as_str()).map_err(Into::into)
@@ -33,11 +51,15 @@ pub(crate) fn try_l1_provider_from_config(cfg: &ZKSyncConfig) -> eyre::Result<Pr
 }
 
 pub(crate) async fn try_l1_signer_from_config(
-    wallet: LocalWallet,
+    wallet: ConfigSigner,
     cfg: &ZKSyncConfig,
 ) -> eyre::Result<SignerMiddleware<impl Middleware, impl Signer>> {
     let l1_provider = try_l1_provider_from_config(cfg)?;
-    let l1_chain_id = l1_provider.get_chainid().await?.as_u64();
+    // The configured chain id is used when set, so the signer can be built offline.
+    let l1_chain_id = match cfg.network.l1_chain_id {
+        Some(l1_chain_id) => l1_chain_id,
+        None => l1_provider.get_chainid().await?.as_u64(),
+    };
     let wallet = wallet.with_chain_id(l1_chain_id);
     Ok(SignerMiddleware::new(l1_provider, wallet))
 }
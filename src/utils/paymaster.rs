@@ -0,0 +1,163 @@
+use crate::utils::{
+    contract::get_fn_selector,
+    signed_tx::{sign_l2_transaction, SignOpts},
+    signer::ConfigSigner,
+};
+use clap::{Parser, ValueEnum};
+use zksync_ethers_rs::{
+    abi::{encode, Token},
+    eip712::PaymasterParams,
+    providers::{Http, Middleware, Provider},
+    types::{Address, H256, U256},
+    zk_wallet::ZKWallet,
+};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum PaymasterType {
+    /// The paymaster pays the fee without conditions.
+    General,
+    /// The paymaster pays the fee in exchange for an allowance of `--paymaster-token`.
+    ApprovalBased,
+}
+
+#[derive(Parser)]
+pub struct PaymasterOpts {
+    #[arg(long, help = "Paymaster paying the transaction fee")]
+    pub paymaster: Option<Address>,
+    #[arg(long, value_enum, requires = "paymaster", default_value_t = PaymasterType::General)]
+    pub paymaster_type: PaymasterType,
+    #[arg(
+        long,
+        requires = "paymaster",
+        required_if_eq("paymaster_type", "approval-based"),
+        help = "L2 token the approval based paymaster takes the fee in"
+    )]
+    pub paymaster_token: Option<Address>,
+    #[arg(
+        long,
+        requires = "paymaster_token",
+        required_if_eq("paymaster_type", "approval-based"),
+        value_parser = U256::from_dec_str,
+        help = "Allowance given to the approval based paymaster, in the token smallest unit"
+    )]
+    pub min_allowance: Option<U256>,
+}
+
+impl PaymasterOpts {
+    /// The paymaster params with the `general(bytes)` or
+    /// `approvalBased(address,uint256,bytes)` paymaster flow input, if a paymaster is set.
+    pub fn paymaster_params(&self) -> eyre::Result<Option<PaymasterParams>> {
+        let Some(paymaster) = self.paymaster else {
+            return Ok(None);
+        };
+        let paymaster_input = match self.paymaster_type {
+            PaymasterType::General => {
+                let mut input = get_fn_selector("general(bytes)").to_vec();
+                input.extend(encode(&[Token::Bytes(Vec::new())]));
+                input
+            }
+            PaymasterType::ApprovalBased => {
+                let token = self
+                    .paymaster_token
+                    .ok_or_else(|| eyre::eyre!("Approval based paymasters need a token"))?;
+                let min_allowance = self.min_allowance.ok_or_else(|| {
+                    eyre::eyre!("Approval based paymasters need a minimal allowance")
+                })?;
+                let mut input = get_fn_selector("approvalBased(address,uint256,bytes)").to_vec();
+                input.extend(encode(&[
+                    Token::Address(token),
+                    Token::Uint(min_allowance),
+                    Token::Bytes(Vec::new()),
+                ]));
+                input
+            }
+        };
+        Ok(Some(PaymasterParams {
+            paymaster,
+            paymaster_input,
+        }))
+    }
+}
+
+/// Sends an EIP-712 L2 transaction whose fee is paid by the given paymaster.
+pub(crate) async fn send_l2_transaction_with_paymaster(
+    zk_wallet: &ZKWallet<Provider<Http>, ConfigSigner>,
+    to: Address,
+    value: U256,
+    data: Vec<u8>,
+    paymaster_params: PaymasterParams,
+) -> eyre::Result<H256> {
+    let signed_tx = sign_l2_transaction(
+        zk_wallet,
+        to,
+        value,
+        data,
+        Some(paymaster_params),
+        &SignOpts::default(),
+    )
+    .await?;
+    let pending_transaction = zk_wallet
+        .l2_provider()
+        .send_raw_transaction(signed_tx.raw_transaction)
+        .await?;
+    // Waits for the receipt like the `ZKWallet` transfers and withdrawals do.
+    let receipt = pending_transaction
+        .await?
+        .ok_or_else(|| eyre::eyre!("No transaction receipt"))?;
+    Ok(receipt.transaction_hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PaymasterOpts;
+    use crate::utils::contract::get_fn_selector;
+    use clap::Parser;
+    use zksync_ethers_rs::{
+        abi::{encode, Token},
+        types::{Address, U256},
+    };
+
+    const PAYMASTER: &str = "0x0000000000000000000000000000000000000001";
+    const TOKEN: &str = "0x0000000000000000000000000000000000000002";
+
+    #[test]
+    fn approval_based_input_has_the_min_allowance() -> eyre::Result<()> {
+        let paymaster_opts = PaymasterOpts::try_parse_from([
+            "zks",
+            "--paymaster",
+            PAYMASTER,
+            "--paymaster-type",
+            "approval-based",
+            "--paymaster-token",
+            TOKEN,
+            "--min-allowance",
+            "1000",
+        ])?;
+        let paymaster_params = paymaster_opts
+            .paymaster_params()?
+            .ok_or_else(|| eyre::eyre!("No paymaster params"))?;
+        let mut expected_input = get_fn_selector("approvalBased(address,uint256,bytes)").to_vec();
+        expected_input.extend(encode(&[
+            Token::Address(TOKEN.parse::<Address>()?),
+            Token::Uint(U256::from(1000_u64)),
+            Token::Bytes(Vec::new()),
+        ]));
+        assert_eq!(paymaster_params.paymaster, PAYMASTER.parse::<Address>()?);
+        assert_eq!(paymaster_params.paymaster_input, expected_input);
+        Ok(())
+    }
+
+    #[test]
+    fn approval_based_requires_a_min_allowance() {
+        assert!(PaymasterOpts::try_parse_from([
+            "zks",
+            "--paymaster",
+            PAYMASTER,
+            "--paymaster-type",
+            "approval-based",
+            "--paymaster-token",
+            TOKEN,
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::utils::db::types::base_layer_circuit_id;
+use circuit_definitions::{
+    circuit_definitions::{
+        base_layer::{ZkSyncBaseLayerCircuit, ZkSyncBaseLayerProof},
+        recursion_layer::{ZkSyncRecursionLayerProof, ZkSyncRecursiveLayerCircuit},
+    },
+    zkevm_circuits::scheduler::aux::BaseLayerCircuitType,
+};
+use clap::ValueEnum;
+use colored::Colorize;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use zksync_ethers_rs::types::zksync::inputs::WitnessInputData;
+
+// Bucket names used by the prover's file backed object store.
+// From: zksync-era:
+// https://github.com/matter-labs/zksync-era/blob/main/core/lib/object_store/src/raw.rs
+pub const PROVER_JOBS_BUCKET: &str = "prover_jobs_fri";
+pub const PROOFS_BUCKET: &str = "proofs_fri";
+pub const WITNESS_INPUTS_BUCKET: &str = "witness_inputs";
+
+// Mirrors `zksync_prover_fri_types::CircuitWrapper`. bincode only encodes the
+// variant index, so the variant order must match the prover's.
+#[allow(clippy::large_enum_variant, reason = "deserialized once")]
+#[derive(Deserialize)]
+pub enum CircuitWrapper {
+    Base(ZkSyncBaseLayerCircuit),
+    Recursive(ZkSyncRecursiveLayerCircuit),
+}
+
+// Mirrors `zksync_prover_fri_types::FriProofWrapper`.
+#[allow(clippy::large_enum_variant, reason = "deserialized once")]
+#[derive(Deserialize)]
+pub enum FriProofWrapper {
+    Base(ZkSyncBaseLayerProof),
+    Recursive(ZkSyncRecursionLayerProof),
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ArtifactKind {
+    Circuit,
+    Proof,
+    WitnessInputs,
+}
+
+impl ArtifactKind {
+    pub fn bucket(&self) -> &'static str {
+        match self {
+            ArtifactKind::Circuit => PROVER_JOBS_BUCKET,
+            ArtifactKind::Proof => PROOFS_BUCKET,
+            ArtifactKind::WitnessInputs => WITNESS_INPUTS_BUCKET,
+        }
+    }
+
+    /// Infers the artifact kind from the object store bucket the blob lives in.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let bucket = path.parent()?.file_name()?.to_str()?;
+        match bucket {
+            PROVER_JOBS_BUCKET => Some(ArtifactKind::Circuit),
+            PROOFS_BUCKET => Some(ArtifactKind::Proof),
+            WITNESS_INPUTS_BUCKET => Some(ArtifactKind::WitnessInputs),
+            _ => None,
+        }
+    }
+}
+
+pub fn blob_path(object_store: &Path, kind: ArtifactKind, blob_url: &str) -> PathBuf {
+    object_store.join(kind.bucket()).join(blob_url)
+}
+
+pub(crate) fn inspect_artifact(path: &Path, kind: Option<ArtifactKind>) -> eyre::Result<()> {
+    let bytes = std::fs::read(path)
+        .map_err(|e| eyre::eyre!("Failed to read artifact {}: {e}", path.display()))?;
+    println!(
+        "{} {} {}",
+        "=".repeat(8),
+        format!("{}", path.display())
+            .bold()
+            .bright_cyan()
+            .on_black(),
+        "=".repeat(8)
+    );
+    println!("{}: {} bytes", "Size".yellow(), bytes.len());
+    match kind.or_else(|| ArtifactKind::from_path(path)) {
+        Some(ArtifactKind::Circuit) => display_circuit(bincode::deserialize(&bytes)?)?,
+        Some(ArtifactKind::Proof) => display_proof(bincode::deserialize(&bytes)?)?,
+        Some(ArtifactKind::WitnessInputs) => display_witness_inputs(bincode::deserialize(&bytes)?),
+        None => {
+            // Not in a known bucket, try every artifact type.
+            if let Ok(circuit) = bincode::deserialize::<CircuitWrapper>(&bytes) {
+                display_circuit(circuit)?
+            } else if let Ok(proof) = bincode::deserialize::<FriProofWrapper>(&bytes) {
+                display_proof(proof)?
+            } else if let Ok(witness_inputs) = bincode::deserialize::<WitnessInputData>(&bytes) {
+                display_witness_inputs(witness_inputs)
+            } else {
+                eyre::bail!("Could not decode artifact, use --kind to specify its type")
+            }
+        }
+    }
+    Ok(())
+}
+
+/// `BaseLayerCircuitType::from_numeric_value` panics on unknown ids, which blobs of other
+/// prover versions can have.
+fn base_layer_circuit_type(numeric_circuit_type: u8) -> eyre::Result<BaseLayerCircuitType> {
+    base_layer_circuit_id(numeric_circuit_type.into(), None)
+        .map(BaseLayerCircuitType::from_numeric_value)
+        .ok_or_else(|| eyre::eyre!("Unknown base layer circuit type {numeric_circuit_type}"))
+}
+
+fn display_circuit(circuit: CircuitWrapper) -> eyre::Result<()> {
+    match circuit {
+        CircuitWrapper::Base(circuit) => {
+            println!("{}: Circuit (Base Layer)", "Artifact".yellow());
+            println!(
+                "{}: {:?}",
+                "Circuit Type".yellow(),
+                base_layer_circuit_type(circuit.numeric_circuit_type())?
+            );
+            println!(
+                "{}: {}",
+                "Description".yellow(),
+                circuit.short_description()
+            );
+        }
+        CircuitWrapper::Recursive(circuit) => {
+            println!("{}: Circuit (Recursion Layer)", "Artifact".yellow());
+            println!(
+                "{}: {}",
+                "Circuit Type".yellow(),
+                circuit.numeric_circuit_type()
+            );
+            println!(
+                "{}: {}",
+                "Description".yellow(),
+                circuit.short_description()
+            );
+        }
+    }
+    Ok(())
+}
+
+fn display_proof(proof: FriProofWrapper) -> eyre::Result<()> {
+    let proof = match proof {
+        FriProofWrapper::Base(proof) => {
+            println!("{}: Proof (Base Layer)", "Artifact".yellow());
+            println!(
+                "{}: {:?}",
+                "Circuit Type".yellow(),
+                base_layer_circuit_type(proof.numeric_circuit_type())?
+            );
+            proof.into_inner()
+        }
+        FriProofWrapper::Recursive(proof) => {
+            println!("{}: Proof (Recursion Layer)", "Artifact".yellow());
+            println!(
+                "{}: {}",
+                "Circuit Type".yellow(),
+                proof.numeric_circuit_type()
+            );
+            proof.into_inner()
+        }
+    };
+    let config = &proof.proof_config;
+    println!("{}:", "Proof Config".yellow());
+    println!("  - FRI LDE factor: {}", config.fri_lde_factor);
+    println!("  - Merkle tree cap size: {}", config.merkle_tree_cap_size);
+    println!(
+        "  - FRI folding schedule: {:?}",
+        config.fri_folding_schedule
+    );
+    println!("  - Security level: {}", config.security_level);
+    println!("  - PoW bits: {}", config.pow_bits);
+    println!(
+        "{}: {}",
+        "FRI Queries".yellow(),
+        proof.queries_per_fri_repetition.len()
+    );
+    println!(
+        "{} ({}):",
+        "Public Inputs".yellow(),
+        proof.public_inputs.len()
+    );
+    for public_input in proof.public_inputs {
+        println!("  - {public_input:?}");
+    }
+    Ok(())
+}
+
+fn display_witness_inputs(witness_inputs: WitnessInputData) {
+    println!("{}: Witness Inputs", "Artifact".yellow());
+    println!(
+        "{}: {}",
+        "L1 Batch Number".yellow(),
+        witness_inputs.vm_run_data.l1_batch_number
+    );
+    println!(
+        "{}: {:?}",
+        "Protocol Version".yellow(),
+        witness_inputs.vm_run_data.protocol_version
+    );
+    println!(
+        "{}: {}",
+        "Used Bytecodes".yellow(),
+        witness_inputs.vm_run_data.used_bytecodes.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{blob_path, ArtifactKind};
+    use std::path::Path;
+
+    #[test]
+    fn blob_paths_round_trip_to_their_kind() {
+        let object_store = Path::new("/artifacts");
+
+        let proof = blob_path(object_store, ArtifactKind::Proof, "proof_42.bin");
+        assert_eq!(proof, Path::new("/artifacts/proofs_fri/proof_42.bin"));
+        assert!(matches!(
+            ArtifactKind::from_path(&proof),
+            Some(ArtifactKind::Proof)
+        ));
+
+        let witness_inputs = blob_path(
+            object_store,
+            ArtifactKind::WitnessInputs,
+            "witness_inputs_7.bin",
+        );
+        assert_eq!(
+            witness_inputs,
+            Path::new("/artifacts/witness_inputs/witness_inputs_7.bin")
+        );
+        assert!(matches!(
+            ArtifactKind::from_path(&witness_inputs),
+            Some(ArtifactKind::WitnessInputs)
+        ));
+
+        let circuit = blob_path(
+            object_store,
+            ArtifactKind::Circuit,
+            "7_1_3_MainVM_BasicCircuits_0.bin",
+        );
+        assert!(matches!(
+            ArtifactKind::from_path(&circuit),
+            Some(ArtifactKind::Circuit)
+        ));
+        assert!(ArtifactKind::from_path(Path::new("/artifacts/proof_42.bin")).is_none());
+    }
+}
@@ -0,0 +1,299 @@
+use crate::{
+    config::{Database, StuckJobsPolicy},
+    utils::db::{
+        queries::{
+            get_batch_proofs_stuck_at_prover_in_agg_round, get_batch_proofs_stuck_at_wg_round,
+            get_prover_jobs_count_by_status, get_recent_batch_proof_times,
+            get_witness_jobs_count_by_status,
+        },
+        types::{
+            BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
+            RecursionTipWitnessGeneratorJobInfo, SchedulerWitnessGeneratorJobInfo, StuckJob,
+        },
+    },
+};
+use http_body_util::Full;
+use hyper::{body::Bytes, server::conn::http1, service::service_fn, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use prometheus_client::{
+    encoding::{text::encode, EncodeLabelSet},
+    metrics::{
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, Histogram},
+    },
+    registry::Registry,
+};
+use sqlx::{pool::PoolConnection, postgres::PgRow, FromRow, Postgres};
+use std::{collections::BTreeSet, convert::Infallible, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::net::TcpListener;
+use zksync_ethers_rs::types::zksync::{basic_fri_types::AggregationRound, L1BatchNumber};
+
+const AGGREGATION_ROUNDS: [AggregationRound; 5] = [
+    AggregationRound::BasicCircuits,
+    AggregationRound::LeafAggregation,
+    AggregationRound::NodeAggregation,
+    AggregationRound::RecursionTip,
+    AggregationRound::Scheduler,
+];
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RoundLabels {
+    round: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RoundStatusLabels {
+    round: String,
+    status: String,
+}
+
+struct ProverMetrics {
+    up: Gauge,
+    stuck_witness_generator_jobs: Family<RoundLabels, Gauge>,
+    stuck_prover_jobs: Family<RoundLabels, Gauge>,
+    witness_generator_jobs: Family<RoundStatusLabels, Gauge>,
+    prover_jobs: Family<RoundStatusLabels, Gauge>,
+    batch_proof_time_seconds: Histogram,
+}
+
+impl ProverMetrics {
+    fn register(registry: &mut Registry) -> Self {
+        let metrics = Self {
+            up: Gauge::default(),
+            stuck_witness_generator_jobs: Family::default(),
+            stuck_prover_jobs: Family::default(),
+            witness_generator_jobs: Family::default(),
+            prover_jobs: Family::default(),
+            // From 1 minute up to ~34 hours.
+            batch_proof_time_seconds: Histogram::new(exponential_buckets(60.0, 2.0, 12)),
+        };
+        registry.register(
+            "zks_prover_up",
+            "Whether the last collection from the prover DB succeeded",
+            metrics.up.clone(),
+        );
+        registry.register(
+            "zks_prover_stuck_witness_generator_jobs",
+            "Witness generator jobs considered stuck by the stuck jobs policy",
+            metrics.stuck_witness_generator_jobs.clone(),
+        );
+        registry.register(
+            "zks_prover_stuck_prover_jobs",
+            "Prover jobs considered stuck by the stuck jobs policy",
+            metrics.stuck_prover_jobs.clone(),
+        );
+        registry.register(
+            "zks_prover_witness_generator_jobs",
+            "Witness generator jobs by aggregation round and status",
+            metrics.witness_generator_jobs.clone(),
+        );
+        registry.register(
+            "zks_prover_prover_jobs",
+            "Prover jobs by aggregation round and status",
+            metrics.prover_jobs.clone(),
+        );
+        registry.register(
+            "zks_prover_batch_proof_time_seconds",
+            "Time from witness inputs creation to proof compression of proved batches",
+            metrics.batch_proof_time_seconds.clone(),
+        );
+        metrics
+    }
+
+    async fn collect(
+        &self,
+        observed_batches: &mut BTreeSet<L1BatchNumber>,
+        policy: &StuckJobsPolicy,
+        prover_db: &mut PoolConnection<Postgres>,
+    ) -> eyre::Result<()> {
+        for aggregation_round in AGGREGATION_ROUNDS {
+            let stuck_jobs = match aggregation_round {
+                AggregationRound::BasicCircuits => {
+                    count_stuck_wg_jobs::<BasicWitnessGeneratorJobInfo>(
+                        aggregation_round,
+                        policy,
+                        prover_db,
+                    )
+                    .await?
+                }
+                AggregationRound::LeafAggregation => {
+                    count_stuck_wg_jobs::<LeafWitnessGeneratorJobInfo>(
+                        aggregation_round,
+                        policy,
+                        prover_db,
+                    )
+                    .await?
+                }
+                AggregationRound::NodeAggregation => {
+                    count_stuck_wg_jobs::<NodeWitnessGeneratorJobInfo>(
+                        aggregation_round,
+                        policy,
+                        prover_db,
+                    )
+                    .await?
+                }
+                AggregationRound::RecursionTip => {
+                    count_stuck_wg_jobs::<RecursionTipWitnessGeneratorJobInfo>(
+                        aggregation_round,
+                        policy,
+                        prover_db,
+                    )
+                    .await?
+                }
+                AggregationRound::Scheduler => {
+                    count_stuck_wg_jobs::<SchedulerWitnessGeneratorJobInfo>(
+                        aggregation_round,
+                        policy,
+                        prover_db,
+                    )
+                    .await?
+                }
+            };
+            let labels = RoundLabels {
+                round: aggregation_round.to_string(),
+            };
+            self.stuck_witness_generator_jobs
+                .get_or_create(&labels)
+                .set(stuck_jobs);
+            let stuck_prover_jobs =
+                get_batch_proofs_stuck_at_prover_in_agg_round(prover_db, aggregation_round, policy)
+                    .await?
+                    .len();
+            self.stuck_prover_jobs
+                .get_or_create(&labels)
+                .set(i64::try_from(stuck_prover_jobs)?);
+        }
+
+        // Statuses with no jobs left would otherwise keep reporting their last count.
+        self.witness_generator_jobs.clear();
+        for aggregation_round in AGGREGATION_ROUNDS {
+            for (status, count) in
+                get_witness_jobs_count_by_status(aggregation_round, prover_db).await?
+            {
+                self.witness_generator_jobs
+                    .get_or_create(&RoundStatusLabels {
+                        round: aggregation_round.to_string(),
+                        status,
+                    })
+                    .set(count);
+            }
+        }
+        self.prover_jobs.clear();
+        for (aggregation_round, status, count) in get_prover_jobs_count_by_status(prover_db).await?
+        {
+            self.prover_jobs
+                .get_or_create(&RoundStatusLabels {
+                    round: aggregation_round.to_string(),
+                    status,
+                })
+                .set(count);
+        }
+
+        // Batches can be compressed out of order, so the ones already observed are tracked
+        // by number. Only the batches proved within the last day are kept, older ones are not
+        // returned anymore. They are only kept in memory, like the histogram itself: a
+        // restarted exporter starts over with the batches proved within the last day.
+        let mut recent_batches = BTreeSet::new();
+        for (l1_batch_number, proof_time_secs) in get_recent_batch_proof_times(prover_db).await? {
+            if !observed_batches.contains(&l1_batch_number) {
+                self.batch_proof_time_seconds
+                    .observe(f64::from(proof_time_secs));
+            }
+            recent_batches.insert(l1_batch_number);
+        }
+        *observed_batches = recent_batches;
+        Ok(())
+    }
+}
+
+async fn count_stuck_wg_jobs<WG>(
+    aggregation_round: AggregationRound,
+    policy: &StuckJobsPolicy,
+    prover_db: &mut PoolConnection<Postgres>,
+) -> eyre::Result<i64>
+where
+    WG: for<'row> FromRow<'row, PgRow> + StuckJob,
+{
+    let stuck_jobs: Vec<WG> =
+        get_batch_proofs_stuck_at_wg_round(aggregation_round, policy, prover_db).await?;
+    Ok(i64::try_from(stuck_jobs.len())?)
+}
+
+/// Periodically collects prover DB health metrics and serves them on `/metrics`.
+pub(crate) async fn run_exporter(
+    listen: SocketAddr,
+    interval: Duration,
+    prover_db: Database,
+    policy: StuckJobsPolicy,
+) -> eyre::Result<()> {
+    let mut registry = Registry::default();
+    let metrics = ProverMetrics::register(&mut registry);
+    let registry = Arc::new(registry);
+
+    tokio::spawn(async move {
+        let mut observed_batches = BTreeSet::new();
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let result = match prover_db.acquire().await {
+                Ok(mut connection) => {
+                    metrics
+                        .collect(&mut observed_batches, &policy, &mut connection)
+                        .await
+                }
+                Err(e) => Err(e.into()),
+            };
+            match result {
+                Ok(()) => metrics.up.set(1),
+                Err(e) => {
+                    tracing::error!("Failed to collect prover metrics: {e:?}");
+                    metrics.up.set(0);
+                }
+            }
+        }
+    });
+
+    let listener = TcpListener::bind(listen).await?;
+    println!("Serving prover metrics on http://{listen}/metrics");
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let registry = Arc::clone(&registry);
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let registry = Arc::clone(&registry);
+                async move { serve_metrics(request, &registry) }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                tracing::error!("Failed to serve metrics connection: {e:?}");
+            }
+        });
+    }
+}
+
+fn serve_metrics<B>(
+    request: Request<B>,
+    registry: &Registry,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let mut response = Response::new(Full::default());
+    if request.uri().path() != "/metrics" {
+        *response.status_mut() = StatusCode::NOT_FOUND;
+        return Ok(response);
+    }
+    let mut buffer = String::new();
+    if encode(&mut buffer, registry).is_err() {
+        *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+        return Ok(response);
+    }
+    *response.body_mut() = Full::new(Bytes::from(buffer));
+    response.headers_mut().insert(
+        hyper::header::CONTENT_TYPE,
+        hyper::header::HeaderValue::from_static(
+            "application/openmetrics-text; version=1.0.0; charset=utf-8",
+        ),
+    );
+    Ok(response)
+}
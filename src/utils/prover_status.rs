@@ -3,9 +3,10 @@ use super::db::{
     types::{
         BasicWitnessGeneratorJobInfo, LeafWitnessGeneratorJobInfo, NodeWitnessGeneratorJobInfo,
         ProofCompressionJobInfo, ProverJobFriInfo, RecursionTipWitnessGeneratorJobInfo,
-        SchedulerWitnessGeneratorJobInfo, StageFlags,
+        SchedulerWitnessGeneratorJobInfo, StageFlags, StuckJob,
     },
 };
+use crate::config::StuckJobsPolicy;
 use circuit_definitions::zkevm_circuits::scheduler::aux::BaseLayerCircuitType;
 use colored::Colorize;
 use sqlx::{pool::PoolConnection, Postgres};
@@ -198,7 +199,7 @@ impl StageInfo {
         }
     }
 
-    pub fn prover_jobs_status(&self, max_attempts: u32) -> Option<Status> {
+    pub fn prover_jobs_status(&self, policy: &StuckJobsPolicy) -> Option<Status> {
         match self.clone() {
             StageInfo::BasicWitnessGenerator {
                 prover_jobs_info, ..
@@ -208,74 +209,71 @@ impl StageInfo {
             }
             | StageInfo::NodeWitnessGenerator {
                 prover_jobs_info, ..
-            } => Some(get_prover_jobs_status_from_vec(
-                &prover_jobs_info,
-                max_attempts,
-            )),
+            } => Some(get_prover_jobs_status_from_vec(&prover_jobs_info, policy)),
             StageInfo::RecursionTipWitnessGenerator(_)
             | StageInfo::SchedulerWitnessGenerator(_)
             | StageInfo::Compressor(_) => None,
         }
     }
 
-    pub fn witness_generator_jobs_status(&self, max_attempts: u32) -> Status {
+    pub fn witness_generator_jobs_status(&self, policy: &StuckJobsPolicy) -> Status {
         match self.clone() {
             StageInfo::BasicWitnessGenerator {
                 witness_generator_job_info,
                 ..
             } => witness_generator_job_info
                 .map(|witness_generator_job_info| {
-                    get_witness_generator_job_status(&witness_generator_job_info, max_attempts)
+                    get_witness_generator_job_status(&witness_generator_job_info, policy)
                 })
                 .unwrap_or_default(),
             StageInfo::LeafWitnessGenerator {
                 witness_generator_jobs_info,
                 ..
-            } => {
-                get_witness_generator_job_status_from_vec(witness_generator_jobs_info, max_attempts)
-            }
+            } => get_witness_generator_job_status_from_vec(witness_generator_jobs_info, policy),
             StageInfo::NodeWitnessGenerator {
                 witness_generator_jobs_info,
                 ..
-            } => {
-                get_witness_generator_job_status_from_vec(witness_generator_jobs_info, max_attempts)
-            }
+            } => get_witness_generator_job_status_from_vec(witness_generator_jobs_info, policy),
             StageInfo::RecursionTipWitnessGenerator(witness_generator_job_info) => {
                 witness_generator_job_info
                     .map(|witness_generator_job_info| {
-                        get_witness_generator_job_status(&witness_generator_job_info, max_attempts)
+                        get_witness_generator_job_status(&witness_generator_job_info, policy)
                     })
                     .unwrap_or_default()
             }
             StageInfo::SchedulerWitnessGenerator(witness_generator_job_info) => {
                 witness_generator_job_info
                     .map(|witness_generator_job_info| {
-                        get_witness_generator_job_status(&witness_generator_job_info, max_attempts)
+                        get_witness_generator_job_status(&witness_generator_job_info, policy)
                     })
                     .unwrap_or_default()
             }
-            StageInfo::Compressor(status) => status
-                .map(|job| Status::from(job._status))
+            StageInfo::Compressor(job) => job
+                .map(|job| {
+                    if policy.is_stuck(&job) {
+                        Status::Stuck
+                    } else {
+                        Status::from(job._status)
+                    }
+                })
                 .unwrap_or_default(),
         }
     }
 }
 
-pub fn get_witness_generator_job_status(data: &impl Stallable, max_attempts: u32) -> Status {
-    let status = data.get_status();
-    if matches!(
-        status,
-        WitnessJobStatus::Failed(_) | WitnessJobStatus::InProgress,
-    ) && data.get_attempts() >= max_attempts
-    {
+pub fn get_witness_generator_job_status(
+    data: &(impl Stallable + StuckJob),
+    policy: &StuckJobsPolicy,
+) -> Status {
+    if policy.is_stuck(data) {
         return Status::Stuck;
     }
-    Status::from(status)
+    Status::from(data.get_status())
 }
 
 pub fn get_witness_generator_job_status_from_vec(
-    prover_jobs: Vec<impl Stallable>,
-    max_attempts: u32,
+    prover_jobs: Vec<impl Stallable + StuckJob>,
+    policy: &StuckJobsPolicy,
 ) -> Status {
     if prover_jobs.is_empty() {
         Status::JobsNotFound
@@ -284,12 +282,7 @@ pub fn get_witness_generator_job_status_from_vec(
         .all(|job| matches!(job.get_status(), WitnessJobStatus::WaitingForProofs))
     {
         Status::WaitingForProofs
-    } else if prover_jobs.iter().any(|job| {
-        matches!(
-            job.get_status(),
-            WitnessJobStatus::Failed(_) | WitnessJobStatus::InProgress,
-        ) && job.get_attempts() >= max_attempts
-    }) {
+    } else if prover_jobs.iter().any(|job| policy.is_stuck(job)) {
         Status::Stuck
     } else if prover_jobs.iter().all(|job| {
         matches!(job.get_status(), WitnessJobStatus::Queued)
@@ -306,12 +299,8 @@ pub fn get_witness_generator_job_status_from_vec(
     }
 }
 
-pub fn get_prover_job_status(prover_jobs: ProverJobFriInfo, max_attempts: u32) -> Status {
-    if matches!(
-        prover_jobs._status,
-        ProverJobStatus::Failed(_) | ProverJobStatus::InProgress(_),
-    ) && prover_jobs._attempts >= max_attempts
-    {
+pub fn get_prover_job_status(prover_jobs: ProverJobFriInfo, policy: &StuckJobsPolicy) -> Status {
+    if policy.is_stuck(&prover_jobs) {
         return Status::Stuck;
     }
     Status::from(prover_jobs._status)
@@ -319,16 +308,11 @@ pub fn get_prover_job_status(prover_jobs: ProverJobFriInfo, max_attempts: u32) -
 
 pub fn get_prover_jobs_status_from_vec(
     prover_jobs: &[ProverJobFriInfo],
-    max_attempts: u32,
+    policy: &StuckJobsPolicy,
 ) -> Status {
     if prover_jobs.is_empty() {
         Status::JobsNotFound
-    } else if prover_jobs.iter().any(|job| {
-        matches!(
-            job._status,
-            ProverJobStatus::Failed(_) | ProverJobStatus::InProgress(_),
-        ) && job._attempts >= max_attempts
-    }) {
+    } else if prover_jobs.iter().any(|job| policy.is_stuck(job)) {
         Status::Stuck
     } else if prover_jobs
         .iter()
@@ -438,7 +422,7 @@ pub async fn get_batches_data(
 
 // Display functions
 
-pub(crate) fn display_batch_status(batch_data: BatchData, flags: u32) {
+pub(crate) fn display_batch_status(batch_data: BatchData, flags: u32, policy: &StuckJobsPolicy) {
     let stages = [
         (StageFlags::Bwg, batch_data.basic_witness_generator),
         (StageFlags::Lwg, batch_data.leaf_witness_generator),
@@ -450,15 +434,14 @@ pub(crate) fn display_batch_status(batch_data: BatchData, flags: u32) {
 
     for (flag, stage) in stages {
         if flags == 0 || flags & flag.as_u32() != 0 {
-            display_status_for_stage(stage);
+            display_status_for_stage(stage, policy);
         }
     }
 }
 
-fn display_status_for_stage(stage_info: StageInfo) {
-    let max_attempts = 10;
+fn display_status_for_stage(stage_info: StageInfo, policy: &StuckJobsPolicy) {
     display_aggregation_round(&stage_info);
-    let status = stage_info.witness_generator_jobs_status(max_attempts);
+    let status = stage_info.witness_generator_jobs_status(policy);
     match status {
         Status::Custom(msg) => {
             println!("{}: {} \n", stage_info.to_string().bold(), msg);
@@ -468,7 +451,7 @@ fn display_status_for_stage(stage_info: StageInfo) {
         }
         Status::InProgress | Status::Successful => {
             println!("{}: {}", stage_info.to_string().bold(), status);
-            if let Some(job_status) = stage_info.prover_jobs_status(max_attempts) {
+            if let Some(job_status) = stage_info.prover_jobs_status(policy) {
                 println!("> {}: {}", "Prover Jobs".to_owned().bold(), job_status);
             }
         }
@@ -487,7 +470,11 @@ fn display_aggregation_round(stage_info: &StageInfo) {
     };
 }
 
-pub(crate) fn display_batch_info(batch_data: BatchData, flags: u32) -> eyre::Result<()> {
+pub(crate) fn display_batch_info(
+    batch_data: BatchData,
+    flags: u32,
+    policy: &StuckJobsPolicy,
+) -> eyre::Result<()> {
     let stages = [
         (StageFlags::Bwg, batch_data.basic_witness_generator),
         (StageFlags::Lwg, batch_data.leaf_witness_generator),
@@ -499,16 +486,15 @@ pub(crate) fn display_batch_info(batch_data: BatchData, flags: u32) -> eyre::Res
 
     for (flag, stage) in stages {
         if flags == 0 || flags & flag.as_u32() != 0 {
-            display_info_for_stage(stage)?;
+            display_info_for_stage(stage, policy)?;
         }
     }
     Ok(())
 }
 
-fn display_info_for_stage(stage_info: StageInfo) -> eyre::Result<()> {
-    let max_attempts = 10;
+fn display_info_for_stage(stage_info: StageInfo, policy: &StuckJobsPolicy) -> eyre::Result<()> {
     display_aggregation_round(&stage_info);
-    let status = stage_info.witness_generator_jobs_status(max_attempts);
+    let status = stage_info.witness_generator_jobs_status(policy);
     match status {
         Status::Custom(msg) => {
             println!("{}: {}", stage_info.to_string().bold(), msg);
@@ -522,27 +508,21 @@ fn display_info_for_stage(stage_info: StageInfo) -> eyre::Result<()> {
                 StageInfo::BasicWitnessGenerator {
                     prover_jobs_info, ..
                 } => {
-                    display_prover_jobs_info(prover_jobs_info, max_attempts)?;
+                    display_prover_jobs_info(prover_jobs_info, policy)?;
                 }
                 StageInfo::LeafWitnessGenerator {
                     witness_generator_jobs_info,
                     prover_jobs_info,
                 } => {
-                    display_leaf_witness_generator_jobs_info(
-                        witness_generator_jobs_info,
-                        max_attempts,
-                    )?;
-                    display_prover_jobs_info(prover_jobs_info, max_attempts)?;
+                    display_leaf_witness_generator_jobs_info(witness_generator_jobs_info, policy)?;
+                    display_prover_jobs_info(prover_jobs_info, policy)?;
                 }
                 StageInfo::NodeWitnessGenerator {
                     witness_generator_jobs_info,
                     prover_jobs_info,
                 } => {
-                    display_node_witness_generator_jobs_info(
-                        witness_generator_jobs_info,
-                        max_attempts,
-                    )?;
-                    display_prover_jobs_info(prover_jobs_info, max_attempts)?;
+                    display_node_witness_generator_jobs_info(witness_generator_jobs_info, policy)?;
+                    display_prover_jobs_info(prover_jobs_info, policy)?;
                 }
                 _ => (),
             }
@@ -558,7 +538,7 @@ fn display_info_for_stage(stage_info: StageInfo) -> eyre::Result<()> {
                 }
                 | StageInfo::NodeWitnessGenerator {
                     prover_jobs_info, ..
-                } => display_prover_jobs_info(prover_jobs_info, max_attempts)?,
+                } => display_prover_jobs_info(prover_jobs_info, policy)?,
                 _ => (),
             }
         }
@@ -568,19 +548,15 @@ fn display_info_for_stage(stage_info: StageInfo) -> eyre::Result<()> {
 
 fn display_leaf_witness_generator_jobs_info(
     mut jobs_info: Vec<LeafWitnessGeneratorJobInfo>,
-    max_attempts: u32,
+    policy: &StuckJobsPolicy,
 ) -> eyre::Result<()> {
     jobs_info.sort_by_key(|job| job._circuit_id);
 
     for job in jobs_info {
         println!(
             "   > {}: {}",
-            format!(
-                "{:?}",
-                BaseLayerCircuitType::from_numeric_value(job._circuit_id.try_into()?)
-            )
-            .bold(),
-            get_witness_generator_job_status(&job, max_attempts)
+            circuit_type_name(job.base_layer_circuit_type(), job._circuit_id).bold(),
+            get_witness_generator_job_status(&job, policy)
         )
     }
     Ok(())
@@ -588,19 +564,15 @@ fn display_leaf_witness_generator_jobs_info(
 
 fn display_node_witness_generator_jobs_info(
     mut jobs_info: Vec<NodeWitnessGeneratorJobInfo>,
-    max_attempts: u32,
+    policy: &StuckJobsPolicy,
 ) -> eyre::Result<()> {
     jobs_info.sort_by_key(|job| job._circuit_id);
 
     for job in jobs_info {
         println!(
             "   > {}: {}",
-            format!(
-                "{:?}",
-                BaseLayerCircuitType::from_numeric_value(job._circuit_id.try_into()?)
-            )
-            .bold(),
-            get_witness_generator_job_status(&job, max_attempts)
+            circuit_type_name(job.base_layer_circuit_type(), job._circuit_id).bold(),
+            get_witness_generator_job_status(&job, policy)
         )
     }
     Ok(())
@@ -608,9 +580,9 @@ fn display_node_witness_generator_jobs_info(
 
 fn display_prover_jobs_info(
     prover_jobs_info: Vec<ProverJobFriInfo>,
-    max_attempts: u32,
+    policy: &StuckJobsPolicy,
 ) -> eyre::Result<()> {
-    let prover_jobs_status = get_prover_jobs_status_from_vec(&prover_jobs_info, max_attempts);
+    let prover_jobs_status = get_prover_jobs_status_from_vec(&prover_jobs_info, policy);
 
     if matches!(
         prover_jobs_status,
@@ -637,25 +609,120 @@ fn display_prover_jobs_info(
     });
 
     for (circuit_id, prover_jobs_info) in jobs_by_circuit_id {
-        let status = get_prover_jobs_status_from_vec(&prover_jobs_info, max_attempts);
+        let status = get_prover_jobs_status_from_vec(&prover_jobs_info, policy);
+        let circuit_type = prover_jobs_info
+            .first()
+            .and_then(ProverJobFriInfo::base_layer_circuit_type);
         println!(
             "   > {}: {}",
-            format!(
-                "{:?}",
-                BaseLayerCircuitType::from_numeric_value(circuit_id.try_into()?)
-            )
-            .bold(),
+            circuit_type_name(circuit_type, circuit_id).bold(),
             status
         );
         match status {
             Status::InProgress => display_job_status_count(prover_jobs_info),
-            Status::Stuck => display_stuck_jobs(prover_jobs_info, max_attempts),
+            Status::Stuck => display_stuck_jobs(prover_jobs_info, policy),
             _ => (),
         }
     }
     Ok(())
 }
 
+fn circuit_type_name(circuit_type: Option<BaseLayerCircuitType>, circuit_id: u32) -> String {
+    match circuit_type {
+        Some(circuit_type) => format!("{circuit_type:?}"),
+        None => format!("Unknown circuit {circuit_id}"),
+    }
+}
+
+/// Displays, for every base layer circuit of the batch, the status, attempts and time
+/// taken of its prover jobs in each aggregation round.
+pub(crate) fn display_batch_circuits_status(batch_data: BatchData, policy: &StuckJobsPolicy) {
+    println!(
+        "{} {} {}",
+        "=".repeat(8),
+        format!("Batch {:0>5} Circuits", batch_data.batch_number.0)
+            .bold()
+            .bright_cyan()
+            .on_black(),
+        "=".repeat(8)
+    );
+
+    let mut jobs_by_circuit: BTreeMap<(Option<u8>, u8, u32), Vec<ProverJobFriInfo>> =
+        BTreeMap::new();
+    for stage in [
+        batch_data.basic_witness_generator,
+        batch_data.leaf_witness_generator,
+        batch_data.node_witness_generator,
+    ] {
+        if let StageInfo::BasicWitnessGenerator {
+            prover_jobs_info, ..
+        }
+        | StageInfo::LeafWitnessGenerator {
+            prover_jobs_info, ..
+        }
+        | StageInfo::NodeWitnessGenerator {
+            prover_jobs_info, ..
+        } = stage
+        {
+            for job in prover_jobs_info {
+                #[allow(clippy::as_conversions, reason = "AggregationRound is an enum of u8s")]
+                let key = (
+                    job.base_layer_circuit_id(),
+                    job._aggregation_round as u8,
+                    job._circuit_id,
+                );
+                jobs_by_circuit.entry(key).or_default().push(job);
+            }
+        }
+    }
+
+    if jobs_by_circuit.is_empty() {
+        println!("No prover jobs found");
+        return;
+    }
+
+    println!(
+        "| {:<28} | {:^18} | {:^6} | {:^20} | {:^8} | {:^18} |",
+        "Circuit", "Round", "Jobs", "Status", "Attempts", "Time Taken (max)"
+    );
+    let mut previous_circuit = None;
+    for ((base_layer_circuit_id, _, circuit_id), jobs) in jobs_by_circuit {
+        let Some(first_job) = jobs.first() else {
+            continue;
+        };
+        let circuit = if previous_circuit == Some(base_layer_circuit_id) {
+            String::new()
+        } else {
+            circuit_type_name(first_job.base_layer_circuit_type(), circuit_id)
+        };
+        previous_circuit = Some(base_layer_circuit_id);
+        let aggregation_round = first_job._aggregation_round;
+        let status = match get_prover_jobs_status_from_vec(&jobs, policy) {
+            Status::Custom(msg) => msg,
+            status => status.to_string(),
+        };
+        let attempts = jobs
+            .iter()
+            .map(|job| job._attempts)
+            .max()
+            .unwrap_or_default();
+        let time_taken = jobs
+            .iter()
+            .filter_map(|job| job._time_taken)
+            .max()
+            .map_or_else(|| "-".to_owned(), |t| t.format("%H:%M:%S").to_string());
+        println!(
+            "| {:<28} | {:^18} | {:^6} | {:^20} | {:^8} | {:^18} |",
+            circuit,
+            format!("{aggregation_round:?}"),
+            jobs.len(),
+            status,
+            attempts,
+            time_taken
+        );
+    }
+}
+
 fn display_job_status_count(jobs: Vec<ProverJobFriInfo>) {
     let mut jobs_counts = ExtendedJobCountStatistics::default();
     jobs.iter().for_each(|job| match job._status {
@@ -673,12 +740,9 @@ fn display_job_status_count(jobs: Vec<ProverJobFriInfo>) {
     println!("     - Failed: {}", jobs_counts.failed);
 }
 
-fn display_stuck_jobs(jobs: Vec<ProverJobFriInfo>, max_attempts: u32) {
+fn display_stuck_jobs(jobs: Vec<ProverJobFriInfo>, policy: &StuckJobsPolicy) {
     jobs.iter().for_each(|job| {
-        if matches!(
-            get_prover_job_status(job.clone(), max_attempts),
-            Status::Stuck
-        ) {
+        if matches!(get_prover_job_status(job.clone(), policy), Status::Stuck) {
             println!(
                 "     - Prover Job: {} stuck after {} attempts",
                 job._id, job._attempts
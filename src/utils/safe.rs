@@ -0,0 +1,317 @@
+use crate::utils::contract::get_fn_selector;
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use zksync_ethers_rs::{
+    abi::{encode, Token},
+    core::utils::keccak256,
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+        Signature, H256, U256,
+    },
+};
+
+const DOMAIN_SEPARATOR_TYPE: &str = "EIP712Domain(uint256 chainId,address verifyingContract)";
+const SAFE_TX_TYPE: &str = "SafeTx(address to,uint256 value,bytes data,uint8 operation,uint256 safeTxGas,uint256 baseGas,uint256 gasPrice,address gasToken,address refundReceiver,uint256 nonce)";
+/// Added to `v` by the Safe for signatures over the `eth_sign` prefixed hash.
+const ETH_SIGN_V_OFFSET: u64 = 4;
+
+/// Options of the transactions sent through a Safe.
+#[derive(Parser, Clone, Default)]
+pub struct SafeOpts {
+    #[arg(
+        long = "safe-signature",
+        value_name = "FILE",
+        help = "Signature of another Safe owner, written with --safe-signature-out"
+    )]
+    pub safe_signatures: Vec<PathBuf>,
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Only sign the Safe transaction and write the signature to FILE"
+    )]
+    pub safe_signature_out: Option<PathBuf>,
+    #[arg(
+        long,
+        value_parser = U256::from_dec_str,
+        help = "Nonce of the Safe transaction, defaults to the current Safe nonce"
+    )]
+    pub safe_nonce: Option<U256>,
+}
+
+/// A Safe owner signature of a Safe transaction, exchanged between the owners as a file.
+#[derive(Serialize, Deserialize)]
+pub struct SafeSignature {
+    pub safe: Address,
+    pub safe_tx_hash: H256,
+    pub signer: Address,
+    pub signature: Bytes,
+}
+
+impl SafeSignature {
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        println!(
+            "Signature of {:?} for Safe transaction {:?} written to {}",
+            self.signer,
+            self.safe_tx_hash,
+            path.display()
+        );
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| eyre::eyre!("Invalid Safe signature file {}: {e}", path.display()))
+    }
+
+    /// The address that signed the Safe transaction hash.
+    fn recover_signer(&self) -> eyre::Result<Address> {
+        let mut signature = Signature::try_from(self.signature.as_ref())?;
+        if signature.v > 30 {
+            signature.v -= ETH_SIGN_V_OFFSET;
+            Ok(signature.recover(self.safe_tx_hash.as_bytes())?)
+        } else {
+            Ok(signature.recover(self.safe_tx_hash)?)
+        }
+    }
+}
+
+/// A Safe transaction calling `to`, without gas refund, i.e. the gas is paid by the account
+/// executing it.
+pub struct SafeTransaction {
+    pub safe: Address,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    pub nonce: U256,
+}
+
+impl SafeTransaction {
+    /// The EIP-712 hash signed by the Safe owners.
+    pub fn hash(&self, chain_id: u64) -> H256 {
+        let domain_separator = keccak256(encode(&[
+            Token::FixedBytes(keccak256(DOMAIN_SEPARATOR_TYPE).to_vec()),
+            Token::Uint(chain_id.into()),
+            Token::Address(self.safe),
+        ]));
+        let safe_tx_struct_hash = keccak256(encode(&[
+            Token::FixedBytes(keccak256(SAFE_TX_TYPE).to_vec()),
+            Token::Address(self.to),
+            Token::Uint(self.value),
+            Token::FixedBytes(keccak256(&self.data).to_vec()),
+            // Call operation, no safeTxGas, baseGas nor gasPrice
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Uint(self.nonce),
+        ]));
+        let mut digest_input = vec![0x19_u8, 0x01_u8];
+        digest_input.extend(domain_separator);
+        digest_input.extend(safe_tx_struct_hash);
+        keccak256(digest_input).into()
+    }
+
+    /// The `execTransaction` calldata, the Safe requires the signatures sorted by signer.
+    pub fn exec_transaction_calldata(&self, mut signatures: Vec<SafeSignature>) -> Bytes {
+        signatures.sort_by_key(|signature| signature.signer);
+        let packed_signatures: Vec<u8> = signatures
+            .iter()
+            .flat_map(|signature| signature.signature.to_vec())
+            .collect();
+        let mut calldata = get_fn_selector("execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)").to_vec();
+        calldata.extend(encode(&[
+            Token::Address(self.to),
+            Token::Uint(self.value),
+            Token::Bytes(self.data.to_vec()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Uint(U256::zero()),
+            Token::Address(Address::zero()),
+            Token::Address(Address::zero()),
+            Token::Bytes(packed_signatures),
+        ]));
+        calldata.into()
+    }
+}
+
+/// Calls a Safe view function, with its ABI encoded arguments.
+async fn safe_call(
+    safe: Address,
+    function_signature: &str,
+    args: &[Token],
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<U256> {
+    let mut calldata = get_fn_selector(function_signature).to_vec();
+    calldata.extend(encode(args));
+    let call: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(safe)
+        .data(Bytes::from(calldata))
+        .into();
+    let result = l1_provider.call(&call, None).await?;
+    let value = result.get(..32).ok_or_else(|| {
+        eyre::eyre!("{function_signature} returned no value, is {safe:?} a Safe?")
+    })?;
+    Ok(U256::from_big_endian(value))
+}
+
+/// Whether `address` is one of the Safe owners.
+async fn is_safe_owner(
+    safe: Address,
+    address: Address,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<bool> {
+    Ok(!safe_call(
+        safe,
+        "isOwner(address)",
+        &[Token::Address(address)],
+        l1_provider,
+    )
+    .await?
+    .is_zero())
+}
+
+/// Signs the Safe transaction hash as an `eth_sign` message, which any signer supports.
+async fn sign_safe_transaction<S: Signer>(
+    safe: Address,
+    safe_tx_hash: H256,
+    signer: &S,
+) -> eyre::Result<SafeSignature> {
+    let mut signature = signer
+        .sign_message(safe_tx_hash)
+        .await
+        .map_err(|e| eyre::eyre!("Failed to sign the Safe transaction: {e}"))?;
+    signature.v += ETH_SIGN_V_OFFSET;
+    Ok(SafeSignature {
+        safe,
+        safe_tx_hash,
+        signer: signer.address(),
+        signature: signature.to_vec().into(),
+    })
+}
+
+/// Builds the Safe transaction calling `to` with `data` and collects the owners signatures,
+/// the one of `signer` if it is an owner and the ones of `--safe-signature`. Returns the
+/// `execTransaction` calldata once the Safe threshold is met.
+pub(crate) async fn safe_exec_transaction<S: Signer>(
+    safe: Address,
+    to: Address,
+    data: Bytes,
+    signer: &S,
+    l1_provider: &Provider<Http>,
+    safe_opts: &SafeOpts,
+) -> eyre::Result<Option<Bytes>> {
+    let nonce = match safe_opts.safe_nonce {
+        Some(nonce) => nonce,
+        None => safe_call(safe, "nonce()", &[], l1_provider).await?,
+    };
+    let safe_tx = SafeTransaction {
+        safe,
+        to,
+        value: U256::zero(),
+        data,
+        nonce,
+    };
+    let safe_tx_hash = safe_tx.hash(signer.chain_id());
+    println!("Safe transaction {safe_tx_hash:?} with Safe nonce {nonce}");
+
+    let is_owner = is_safe_owner(safe, signer.address(), l1_provider).await?;
+    if let Some(signature_path) = &safe_opts.safe_signature_out {
+        if !is_owner {
+            eyre::bail!("{:?} is not an owner of Safe {safe:?}", signer.address());
+        }
+        sign_safe_transaction(safe, safe_tx_hash, signer)
+            .await?
+            .write(signature_path)?;
+        return Ok(None);
+    }
+
+    let mut signatures: Vec<SafeSignature> = Vec::new();
+    for signature_path in &safe_opts.safe_signatures {
+        let signature = SafeSignature::read(signature_path)?;
+        if signature.safe != safe || signature.safe_tx_hash != safe_tx_hash {
+            eyre::bail!(
+                "{} signs Safe transaction {:?} of {:?}, not {safe_tx_hash:?}",
+                signature_path.display(),
+                signature.safe_tx_hash,
+                signature.safe
+            );
+        }
+        if signature.recover_signer()? != signature.signer {
+            eyre::bail!(
+                "{} is not a signature of {:?}",
+                signature_path.display(),
+                signature.signer
+            );
+        }
+        if !is_safe_owner(safe, signature.signer, l1_provider).await? {
+            eyre::bail!(
+                "{} is signed by {:?}, not an owner of Safe {safe:?}",
+                signature_path.display(),
+                signature.signer
+            );
+        }
+        if signatures.iter().all(|s| s.signer != signature.signer) {
+            signatures.push(signature);
+        }
+    }
+    if is_owner && signatures.iter().all(|s| s.signer != signer.address()) {
+        signatures.push(sign_safe_transaction(safe, safe_tx_hash, signer).await?);
+    }
+
+    let threshold = safe_call(safe, "getThreshold()", &[], l1_provider).await?;
+    if U256::from(signatures.len()) < threshold {
+        println!(
+            "{} of {threshold} signatures collected, sign it with --safe-signature-out and pass the signatures with --safe-signature",
+            signatures.len()
+        );
+        return Ok(None);
+    }
+    Ok(Some(safe_tx.exec_transaction_calldata(signatures)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SafeTransaction, DOMAIN_SEPARATOR_TYPE, SAFE_TX_TYPE};
+    use std::str::FromStr;
+    use zksync_ethers_rs::{
+        core::utils::keccak256,
+        types::{Address, Bytes, H256, U256},
+    };
+
+    #[test]
+    fn type_hashes_match_the_safe_contract() -> eyre::Result<()> {
+        // DOMAIN_SEPARATOR_TYPEHASH and SAFE_TX_TYPEHASH of Safe.sol.
+        assert_eq!(
+            H256(keccak256(DOMAIN_SEPARATOR_TYPE)),
+            H256::from_str("0x47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218")?
+        );
+        assert_eq!(
+            H256(keccak256(SAFE_TX_TYPE)),
+            H256::from_str("0xbb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn safe_transaction_hash() -> eyre::Result<()> {
+        let safe_tx = SafeTransaction {
+            safe: Address::from_str("0x1111111111111111111111111111111111111111")?,
+            to: Address::from_str("0x2222222222222222222222222222222222222222")?,
+            value: U256::exp10(18_usize),
+            data: Bytes::from_str("0xa9059cbb")?,
+            nonce: U256::from(7_u64),
+        };
+        assert_eq!(
+            safe_tx.hash(1_u64),
+            H256::from_str("0xdf29484fa432453d0a9f37868fa36e992b16209961ada0508fd359a6d3651b9b")?
+        );
+        Ok(())
+    }
+}
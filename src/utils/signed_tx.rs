@@ -0,0 +1,239 @@
+use crate::utils::signer::ConfigSigner;
+use crate::utils::tx::{
+    estimate_l1_fee, estimate_l2_fee, FeeEstimate, DEFAULT_GAS_PER_PUBDATA_LIMIT,
+};
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+use zksync_ethers_rs::{
+    eip712::{Eip712Meta, Eip712Transaction, Eip712TransactionRequest, PaymasterParams},
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, U256,
+    },
+    zk_wallet::ZKWallet,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Layer {
+    L1,
+    L2,
+}
+
+impl Display for Layer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layer::L1 => write!(f, "L1"),
+            Layer::L2 => write!(f, "L2"),
+        }
+    }
+}
+
+/// A transaction signed with `--sign-only`, broadcast later with `zks chain send-raw`.
+#[derive(Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub layer: Layer,
+    pub chain_id: u64,
+    pub from: Address,
+    pub nonce: U256,
+    /// EIP-1559 on L1, EIP-712 on L2.
+    pub raw_transaction: Bytes,
+}
+
+impl SignedTransaction {
+    pub fn write(&self, path: &Path) -> eyre::Result<()> {
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        println!(
+            "Signed {} transaction from {:?} with nonce {} written to {}",
+            self.layer,
+            self.from,
+            self.nonce,
+            path.display()
+        );
+        Ok(())
+    }
+
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        serde_json::from_str(&std::fs::read_to_string(path)?)
+            .map_err(|e| eyre::eyre!("Invalid signed transaction file {}: {e}", path.display()))
+    }
+}
+
+/// Options to sign a transaction without sending it. The nonce, chain id, gas limit and fees
+/// are fetched from the network unless given, so all of them must be set to sign offline.
+#[derive(Parser, Clone, Default)]
+pub struct SignOpts {
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write the signed transaction to FILE instead of sending it"
+    )]
+    pub sign_only: Option<PathBuf>,
+    #[arg(long, requires = "sign_only", value_parser = U256::from_dec_str)]
+    pub nonce: Option<U256>,
+    #[arg(
+        long,
+        requires = "sign_only",
+        help = "Chain id of the signed transaction"
+    )]
+    pub tx_chain_id: Option<u64>,
+    #[arg(long, requires = "sign_only", value_parser = U256::from_dec_str)]
+    pub gas_limit: Option<U256>,
+    #[arg(
+        long,
+        requires = "sign_only",
+        value_parser = U256::from_dec_str,
+        help = "In wei"
+    )]
+    pub max_fee_per_gas: Option<U256>,
+    #[arg(
+        long,
+        requires = "sign_only",
+        value_parser = U256::from_dec_str,
+        help = "In wei"
+    )]
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+impl SignOpts {
+    /// The fee given with the flags, if it does not need to be estimated.
+    fn fee(&self) -> Option<FeeEstimate> {
+        Some(FeeEstimate {
+            gas_limit: self.gas_limit?,
+            gas_per_pubdata: None,
+            max_fee_per_gas: self.max_fee_per_gas?,
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas?,
+        })
+    }
+
+    /// Replaces the estimated values by the ones given with the flags.
+    fn override_fee(&self, fee: FeeEstimate) -> FeeEstimate {
+        FeeEstimate {
+            gas_limit: self.gas_limit.unwrap_or(fee.gas_limit),
+            gas_per_pubdata: fee.gas_per_pubdata,
+            max_fee_per_gas: self.max_fee_per_gas.unwrap_or(fee.max_fee_per_gas),
+            max_priority_fee_per_gas: self
+                .max_priority_fee_per_gas
+                .unwrap_or(fee.max_priority_fee_per_gas),
+        }
+    }
+}
+
+/// Signs an EIP-1559 L1 transaction.
+pub(crate) async fn sign_l1_transaction<S: Signer>(
+    signer: &S,
+    to: Address,
+    value: U256,
+    data: Bytes,
+    l1_provider: &Provider<Http>,
+    sign_opts: &SignOpts,
+) -> eyre::Result<SignedTransaction> {
+    let from = signer.address();
+    let chain_id = sign_opts.tx_chain_id.unwrap_or(signer.chain_id());
+    let nonce = match sign_opts.nonce {
+        Some(nonce) => nonce,
+        None => l1_provider.get_transaction_count(from, None).await?,
+    };
+    let tx = Eip1559TransactionRequest::new()
+        .from(from)
+        .to(to)
+        .value(value)
+        .data(data)
+        .chain_id(chain_id)
+        .nonce(nonce);
+    let fee = match sign_opts.fee() {
+        Some(fee) => fee,
+        None => {
+            let gas_limit = match sign_opts.gas_limit {
+                Some(gas_limit) => gas_limit,
+                None => l1_provider.estimate_gas(&tx.clone().into(), None).await?,
+            };
+            sign_opts.override_fee(estimate_l1_fee(gas_limit, l1_provider).await?)
+        }
+    };
+    let tx: TypedTransaction = tx
+        .gas(fee.gas_limit)
+        .max_fee_per_gas(fee.max_fee_per_gas)
+        .max_priority_fee_per_gas(fee.max_priority_fee_per_gas)
+        .into();
+    let signature = signer
+        .sign_transaction(&tx)
+        .await
+        .map_err(|e| eyre::eyre!("Failed to sign the transaction: {e}"))?;
+    Ok(SignedTransaction {
+        layer: Layer::L1,
+        chain_id,
+        from,
+        nonce,
+        raw_transaction: tx.rlp_signed(&signature),
+    })
+}
+
+/// Signs an EIP-712 L2 transaction, whose fee is paid by the paymaster if one is given.
+pub(crate) async fn sign_l2_transaction(
+    zk_wallet: &ZKWallet<Provider<Http>, ConfigSigner>,
+    to: Address,
+    value: U256,
+    data: Vec<u8>,
+    paymaster_params: Option<PaymasterParams>,
+    sign_opts: &SignOpts,
+) -> eyre::Result<SignedTransaction> {
+    let l2_provider = zk_wallet.l2_provider();
+    let signer = zk_wallet.l2_signer().signer().clone();
+    let from = signer.address();
+    let chain_id = sign_opts.tx_chain_id.unwrap_or(signer.chain_id());
+    let nonce = match sign_opts.nonce {
+        Some(nonce) => nonce,
+        None => l2_provider.get_transaction_count(from, None).await?,
+    };
+    // The paymaster validation is part of the estimated gas.
+    let fee = match sign_opts.fee() {
+        Some(fee) => fee,
+        None => sign_opts.override_fee(
+            estimate_l2_fee(
+                l2_provider,
+                from,
+                to,
+                value,
+                &data,
+                paymaster_params.as_ref(),
+            )
+            .await?,
+        ),
+    };
+
+    let mut eip712_meta = Eip712Meta::new().gas_per_pubdata(
+        fee.gas_per_pubdata
+            .unwrap_or(DEFAULT_GAS_PER_PUBDATA_LIMIT.into()),
+    );
+    if let Some(paymaster_params) = paymaster_params {
+        eip712_meta = eip712_meta.paymaster_params(paymaster_params);
+    }
+    let request = Eip712TransactionRequest::new()
+        .from(from)
+        .to(to)
+        .value(value)
+        .data(data)
+        .nonce(nonce)
+        .chain_id(chain_id)
+        .gas_limit(fee.gas_limit)
+        .max_fee_per_gas(fee.max_fee_per_gas)
+        .max_priority_fee_per_gas(fee.max_priority_fee_per_gas)
+        .custom_data(eip712_meta);
+
+    let signature = signer
+        .sign_typed_data(&Eip712Transaction::try_from(request.clone())?)
+        .await?;
+    Ok(SignedTransaction {
+        layer: Layer::L2,
+        chain_id,
+        from,
+        nonce,
+        raw_transaction: request.rlp_signed(signature)?,
+    })
+}
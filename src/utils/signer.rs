@@ -0,0 +1,163 @@
+use async_trait::async_trait;
+use zksync_ethers_rs::{
+    core::utils::rlp::Rlp,
+    providers::{Http, Provider, ProviderError},
+    signers::{LocalWallet, Signer, WalletError},
+    types::{
+        transaction::{
+            eip2718::{TypedTransaction, TypedTransactionError},
+            eip712::Eip712,
+        },
+        Address, Bytes, Signature, SignatureError,
+    },
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigSignerError {
+    #[error(transparent)]
+    Local(#[from] WalletError),
+    #[error("Remote signer request failed: {0}")]
+    Remote(#[from] ProviderError),
+    #[error("Invalid signature from the remote signer: {0}")]
+    InvalidSignature(#[from] SignatureError),
+    #[error("Invalid signed transaction from the remote signer: {0}")]
+    InvalidSignedTransaction(#[from] TypedTransactionError),
+    #[error(
+        "The remote signer changed the transaction or signed it as another account than {0:?}"
+    )]
+    SignedTransactionMismatch(Address),
+    #[error("The remote signer can't sign EIP-712 typed data")]
+    TypedDataUnsupported,
+}
+
+/// Signer of a configured wallet, either its local key or a remote signer holding it.
+#[derive(Debug, Clone)]
+pub enum ConfigSigner {
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+impl ConfigSigner {
+    /// Fails for remote signers, which can't sign the EIP-712 typed data of the L2
+    /// transactions sent with a paymaster or signed with `--sign-only`.
+    pub fn ensure_typed_data_support(&self) -> eyre::Result<()> {
+        if let ConfigSigner::Remote(_) = self {
+            eyre::bail!("Remote signer wallets can't sign EIP-712 L2 transactions, i.e. the ones with a paymaster or signed with --sign-only, use a local wallet instead");
+        }
+        Ok(())
+    }
+}
+
+impl From<LocalWallet> for ConfigSigner {
+    fn from(wallet: LocalWallet) -> Self {
+        ConfigSigner::Local(wallet)
+    }
+}
+
+#[async_trait]
+impl Signer for ConfigSigner {
+    type Error = ConfigSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            ConfigSigner::Local(wallet) => Ok(wallet.sign_message(message).await?),
+            ConfigSigner::Remote(remote_signer) => remote_signer.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            ConfigSigner::Local(wallet) => Ok(wallet.sign_transaction(tx).await?),
+            ConfigSigner::Remote(remote_signer) => remote_signer.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            ConfigSigner::Local(wallet) => Ok(wallet.sign_typed_data(payload).await?),
+            ConfigSigner::Remote(_) => Err(ConfigSignerError::TypedDataUnsupported),
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            ConfigSigner::Local(wallet) => wallet.address(),
+            ConfigSigner::Remote(remote_signer) => remote_signer.address,
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            ConfigSigner::Local(wallet) => wallet.chain_id(),
+            ConfigSigner::Remote(remote_signer) => remote_signer.chain_id,
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            ConfigSigner::Local(wallet) => ConfigSigner::Local(wallet.with_chain_id(chain_id)),
+            ConfigSigner::Remote(remote_signer) => ConfigSigner::Remote(RemoteSigner {
+                chain_id: chain_id.into(),
+                ..remote_signer
+            }),
+        }
+    }
+}
+
+/// Signer holding the key of `address` behind an `eth_sign`/`eth_signTransaction` JSON-RPC
+/// endpoint, e.g. web3signer or `zks test mock-signer`, so the key never reaches this host.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    client: Provider<Http>,
+    address: Address,
+    chain_id: u64,
+}
+
+impl RemoteSigner {
+    pub fn new(url: &str, address: Address) -> eyre::Result<Self> {
+        Ok(Self {
+            client: Provider::try_from(url)?,
+            address,
+            chain_id: 1,
+        })
+    }
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, ConfigSignerError> {
+        let signature: Bytes = self
+            .client
+            .request(
+                "eth_sign",
+                (self.address, Bytes::from(message.as_ref().to_vec())),
+            )
+            .await?;
+        Ok(Signature::try_from(signature.as_ref())?)
+    }
+
+    async fn sign_transaction(
+        &self,
+        tx: &TypedTransaction,
+    ) -> Result<Signature, ConfigSignerError> {
+        let mut tx = tx.clone();
+        tx.set_from(self.address);
+        if tx.chain_id().is_none() {
+            tx.set_chain_id(self.chain_id);
+        }
+        let signed_tx: Bytes = self.client.request("eth_signTransaction", [tx]).await?;
+        let (_, signature) = TypedTransaction::decode_signed(&Rlp::new(signed_tx.as_ref()))?;
+        // Only the signature is kept, it must sign the transaction as sent to the remote, which
+        // could have filled or changed some of its fields.
+        if signature.recover(tx.sighash())? != self.address {
+            return Err(ConfigSignerError::SignedTransactionMismatch(self.address));
+        }
+        Ok(signature)
+    }
+}
@@ -1,5 +1,8 @@
 use super::balance::{display_balance, get_erc20_balance, get_erc20_balance_decimals_symbol};
-use crate::utils::{contract::*, wallet::new_zkwallet};
+use crate::utils::{
+    contract::*,
+    wallet::{derive_wallet, new_zkwallet},
+};
 use colored::Colorize;
 use eyre::ContextCompat;
 use itertools::Itertools;
@@ -11,7 +14,7 @@ use zksync_ethers_rs::{
     core::{
         k256::ecdsa::SigningKey,
         rand::{thread_rng, Rng},
-        utils::{format_ether, format_units, parse_ether},
+        utils::{format_units, parse_units},
     },
     providers::{Http, Middleware, Provider, ProviderError},
     signers::{LocalWallet, Signer, Wallet},
@@ -122,6 +125,16 @@ pub async fn future_transfer_base_token_back(
         .l2_provider()
         .get_balance(from_wallet.l2_address(), None)
         .await?;
+    let fee = transfer_base_token_back_fee(from_wallet, to_wallet, balance).await?;
+    transfer_base_token_back(from_wallet, to_wallet, balance.saturating_sub(fee)).await
+}
+
+/// The fee of sending `balance` of the base token back to `to_wallet`, with 10% of headroom.
+async fn transfer_base_token_back_fee(
+    from_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+    to_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+    balance: U256,
+) -> eyre::Result<U256> {
     let transfer_tx = TypedTransaction::Eip1559(
         Eip1559TransactionRequest::new()
             .from(from_wallet.l2_address())
@@ -135,10 +148,17 @@ pub async fn future_transfer_base_token_back(
         .div(10_u32)
         .saturating_mul(U256::from(11_u32)); // 10% of headroom
     let gas_price = from_wallet.l2_provider().get_gas_price().await?;
-    let gas = gas_estimate.saturating_mul(gas_price);
+    Ok(gas_estimate.saturating_mul(gas_price))
+}
+
+async fn transfer_base_token_back(
+    from_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+    to_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+    amount: U256,
+) -> eyre::Result<H256> {
     let transfer_hash = from_wallet
         .transfer_base_token(
-            balance.saturating_sub(gas),
+            amount,
             to_wallet.l1_address(),
             // The nonce is not changed since all the transfers are from different wallets
             None,
@@ -147,6 +167,101 @@ pub async fn future_transfer_base_token_back(
     Ok(transfer_hash)
 }
 
+/// Sends every L2 ERC20 balance and then the remaining base token balance of each wallet to
+/// `to_wallet`.
+/// Sweeps every wallet even if some fail, returning the transfer hashes and the wallets
+/// that could not be swept with their error.
+pub async fn sweep_wallets(
+    from_wallets: &[Arc<ZKWallet<Provider<Http>, LocalWallet>>],
+    to_wallet: &Arc<ZKWallet<Provider<Http>, LocalWallet>>,
+) -> eyre::Result<(Vec<H256>, Vec<(Address, eyre::Report)>)> {
+    println!(
+        "{} Sweep from {} wallet to {} wallet.",
+        "[L2->L2]".bold().bright_cyan().on_black(),
+        "each".bold().blue().on_black(),
+        "rich".bold().red().on_black()
+    );
+
+    let mut l2_txs_hashes: Vec<H256> = Vec::new();
+    let mut failed_wallets = Vec::new();
+    let mut set = JoinSet::new();
+
+    for w in from_wallets.iter() {
+        let to_wallet_clone = Arc::clone(to_wallet);
+        let from_wallet_clone = Arc::clone(w);
+        set.spawn(async move {
+            let result = sweep_wallet(&from_wallet_clone, &to_wallet_clone).await;
+            (from_wallet_clone.l2_address(), result)
+        });
+    }
+
+    while let Some(res) = set.join_next().await {
+        match res? {
+            (_, Ok(tx_hashes)) => {
+                l2_txs_hashes.extend(tx_hashes);
+            }
+            (address, Err(e)) => {
+                println!("Error sweeping wallet {address:?}: {e}");
+                failed_wallets.push((address, e));
+            }
+        };
+    }
+
+    Ok((l2_txs_hashes, failed_wallets))
+}
+
+async fn sweep_wallet(
+    from_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+    to_wallet: &ZKWallet<Provider<Http>, LocalWallet>,
+) -> eyre::Result<Vec<H256>> {
+    let mut tx_hashes = Vec::new();
+    let balances = from_wallet
+        .l2_provider()
+        .get_all_account_balances(from_wallet.l2_address())
+        .await?;
+    // ERC20s go first, their transfers are paid with the base token.
+    for (token_address, balance) in balances {
+        if token_address == Address::zero()
+            || token_address == L2_ETH_TOKEN_ADDRESS
+            || balance.is_zero()
+        {
+            continue;
+        }
+        let transfer_hash = from_wallet
+            .transfer_erc20(balance, token_address, to_wallet.l2_address(), None)
+            .await?;
+        println!(
+            "Wallet {:?} swept {balance} of {token_address:?}: {transfer_hash:?}",
+            from_wallet.l2_address()
+        );
+        tx_hashes.push(transfer_hash);
+    }
+    let balance = from_wallet
+        .l2_provider()
+        .get_balance(from_wallet.l2_address(), None)
+        .await?;
+    if balance.is_zero() {
+        return Ok(tx_hashes);
+    }
+    let fee = transfer_base_token_back_fee(from_wallet, to_wallet, balance).await?;
+    // Dust can't pay for its own transfer, the wallet is swept as far as it can be.
+    if balance <= fee {
+        println!(
+            "Wallet {:?} left {balance} of base token, below the transfer fee",
+            from_wallet.l2_address()
+        );
+        return Ok(tx_hashes);
+    }
+    let transfer_hash =
+        transfer_base_token_back(from_wallet, to_wallet, balance.saturating_sub(fee)).await?;
+    println!(
+        "Wallet {:?} swept its base token: {transfer_hash:?}",
+        from_wallet.l2_address()
+    );
+    tx_hashes.push(transfer_hash);
+    Ok(tx_hashes)
+}
+
 pub async fn deposit_base_token(
     from_wallet: &Arc<ZKWallet<Provider<Http>, LocalWallet>>,
     parsed_amount_to_deposit: U256,
@@ -171,12 +286,8 @@ pub async fn get_n_random_wallets(
     l1_provider: &Provider<Http>,
     l2_provider: &Provider<Http>,
 ) -> eyre::Result<Vec<Arc<ZKWallet<Provider<Http>, LocalWallet>>>> {
-    let mut set = JoinSet::new();
-    for i in 1..=number_of_wallets {
-        // These clones are necessary to move the providers into the async block,
-        let l1_clone = l1_provider.clone();
-        let l2_clone = l2_provider.clone();
-        set.spawn(async move {
+    let signers = (1..=number_of_wallets)
+        .map(|i| {
             let local_wallet = LocalWallet::new(&mut thread_rng());
             let pk_bytes = local_wallet.signer().to_bytes();
             let pk = hex::encode(pk_bytes);
@@ -184,8 +295,46 @@ pub async fn get_n_random_wallets(
                 "Wallet [{i:0>3}] addr: {:?} || pk: 0x{pk}",
                 local_wallet.address(),
             );
-            new_zkwallet(local_wallet, &l1_clone, &l2_clone).await
-        });
+            local_wallet
+        })
+        .collect_vec();
+    zkwallets_from_signers(signers, l1_provider, l2_provider).await
+}
+
+/// Derives `number_of_wallets` wallets from `mnemonic`, starting at the `derivation_start` index,
+/// so the same wallets can be recovered after the test run.
+pub async fn get_n_derived_wallets(
+    number_of_wallets: u16,
+    mnemonic: &str,
+    derivation_start: u32,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<Vec<Arc<ZKWallet<Provider<Http>, LocalWallet>>>> {
+    let signers = (0..u32::from(number_of_wallets))
+        .map(|i| {
+            let index = derivation_start
+                .checked_add(i)
+                .ok_or_else(|| eyre::eyre!("Derivation index overflow"))?;
+            let local_wallet = derive_wallet(mnemonic, index)?;
+            println!("Wallet [{index:0>3}] addr: {:?}", local_wallet.address());
+            Ok(local_wallet)
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    zkwallets_from_signers(signers, l1_provider, l2_provider).await
+}
+
+/// Builds the ZKsync wallets of `signers` concurrently.
+pub async fn zkwallets_from_signers(
+    signers: Vec<LocalWallet>,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<Vec<Arc<ZKWallet<Provider<Http>, LocalWallet>>>> {
+    let mut set = JoinSet::new();
+    for signer in signers {
+        // These clones are necessary to move the providers into the async block,
+        let l1_clone = l1_provider.clone();
+        let l2_clone = l2_provider.clone();
+        set.spawn(async move { new_zkwallet(signer, &l1_clone, &l2_clone).await });
     }
     let wallets = set
         .join_all()
@@ -198,6 +347,29 @@ pub async fn get_n_random_wallets(
     Ok(wallets)
 }
 
+/// Derived wallets if a mnemonic is given, random ones otherwise.
+pub async fn get_n_wallets(
+    number_of_wallets: u16,
+    mnemonic: Option<&str>,
+    derivation_start: u32,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<Vec<Arc<ZKWallet<Provider<Http>, LocalWallet>>>> {
+    match mnemonic {
+        Some(mnemonic) => {
+            get_n_derived_wallets(
+                number_of_wallets,
+                mnemonic,
+                derivation_start,
+                l1_provider,
+                l2_provider,
+            )
+            .await
+        }
+        None => get_n_random_wallets(number_of_wallets, l1_provider, l2_provider).await,
+    }
+}
+
 pub async fn display_balances(
     wallets: &[Arc<ZKWallet<Provider<Http>, LocalWallet>>],
 ) -> eyre::Result<()> {
@@ -239,31 +411,32 @@ pub async fn check_balance_and_deposit_or_mint(
         .await?;
 
     if l2_balance.le(&amount) {
-        let (l1_balance, _, token_symbol) = if base_token_address == L2_ETH_TOKEN_ADDRESS {
-            (
-                format_units(
-                    wallet
-                        .l1_provider()
-                        .get_balance(wallet.l1_address(), None)
-                        .await?,
+        let (l1_balance, token_decimals, token_symbol) =
+            if base_token_address == L2_ETH_TOKEN_ADDRESS {
+                (
+                    format_units(
+                        wallet
+                            .l1_provider()
+                            .get_balance(wallet.l1_address(), None)
+                            .await?,
+                        18_i32,
+                    )?,
                     18_i32,
-                )?,
-                18_i32,
-                "ETH".to_owned(),
-            )
-        } else {
-            get_erc20_balance_decimals_symbol(
-                base_token_address,
-                wallet.l1_address(),
-                wallet.l1_provider(),
-            )
-            .await?
-        };
+                    "ETH".to_owned(),
+                )
+            } else {
+                get_erc20_balance_decimals_symbol(
+                    base_token_address,
+                    wallet.l1_address(),
+                    wallet.l1_provider(),
+                )
+                .await?
+            };
 
         spinner.update(spinners::Dots, "Checking L1 Balance", Color::Blue);
 
-        // Here we are assuming that the base token has 18 decimals
-        if parse_ether(&l1_balance)?.le(&amount) {
+        let l1_balance: U256 = parse_units(&l1_balance, token_decimals)?.into();
+        if l1_balance.le(&amount) {
             let mint_amount = amount;
 
             let balance = get_erc20_balance(
@@ -275,7 +448,7 @@ pub async fn check_balance_and_deposit_or_mint(
 
             let msg = format!(
                 "[L1] Balance isn't enough: {balance} {token_symbol} || Minting {} {token_symbol}",
-                format_ether(mint_amount)
+                format_units(mint_amount, token_decimals)?
             );
             spinner.update(spinners::Dots, msg, Color::Blue);
 
@@ -0,0 +1,86 @@
+use crate::utils::config::zks_dir_path;
+use serde::{Deserialize, Serialize};
+use std::{io::Write, path::PathBuf, sync::Arc};
+use zksync_ethers_rs::{
+    core::rand::{thread_rng, Rng},
+    providers::{Http, Provider},
+    signers::LocalWallet,
+    types::Address,
+    zk_wallet::ZKWallet,
+};
+
+/// Keys of the wallets generated for a test run, so their funds can be swept back if the
+/// run is interrupted.
+#[derive(Deserialize, Serialize)]
+pub struct TestRun {
+    pub wallets: Vec<TestRunWallet>,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct TestRunWallet {
+    pub address: Address,
+    pub private_key: String,
+}
+
+impl TestRun {
+    pub fn signers(&self) -> eyre::Result<Vec<LocalWallet>> {
+        self.wallets
+            .iter()
+            .map(|wallet| wallet.private_key.parse().map_err(Into::into))
+            .collect()
+    }
+}
+
+pub fn test_runs_dir_path() -> eyre::Result<PathBuf> {
+    zks_dir_path("test-runs")
+}
+
+pub fn test_run_path(run_id: &str) -> eyre::Result<PathBuf> {
+    Ok(test_runs_dir_path()?.join(format!("{run_id}.json")))
+}
+
+/// Stores the keys of the test wallets, readable by the user only, and returns the run id to
+/// sweep them with.
+pub fn save_test_run(
+    wallets: &[Arc<ZKWallet<Provider<Http>, LocalWallet>>],
+) -> eyre::Result<String> {
+    // The random suffix tells apart the runs started in the same second.
+    let run_id = format!(
+        "{}-{:04x}",
+        chrono::Utc::now().format("%Y%m%d%H%M%S"),
+        thread_rng().gen::<u16>()
+    );
+    let test_run = TestRun {
+        wallets: wallets
+            .iter()
+            .map(|wallet| TestRunWallet {
+                address: wallet.l2_address(),
+                private_key: format!(
+                    "0x{}",
+                    hex::encode(wallet.l2_signer().signer().signer().to_bytes())
+                ),
+            })
+            .collect(),
+    };
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(test_run_path(&run_id)?)?
+        .write_all(serde_json::to_string_pretty(&test_run)?.as_bytes())?;
+    Ok(run_id)
+}
+
+pub fn load_test_run(run_id: &str) -> eyre::Result<TestRun> {
+    let test_run_path = test_run_path(run_id)?;
+    if !test_run_path.exists() {
+        eyre::bail!("Test run {run_id} does not exist");
+    }
+    serde_json::from_str(&std::fs::read_to_string(test_run_path)?).map_err(Into::into)
+}
+
+/// Deletes the keys of a test run, once its wallets are swept.
+pub fn remove_test_run(run_id: &str) -> eyre::Result<()> {
+    std::fs::remove_file(test_run_path(run_id)?).map_err(Into::into)
+}
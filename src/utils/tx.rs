@@ -0,0 +1,219 @@
+use crate::{
+    config::ZKSyncConfig,
+    utils::{
+        config::confirm,
+        safe::{safe_exec_transaction, SafeOpts},
+        signed_tx::{sign_l1_transaction, SignOpts},
+        try_l1_provider_from_config,
+    },
+};
+use clap::Parser;
+use colored::Colorize;
+use zksync_ethers_rs::{
+    core::utils::format_units,
+    eip712::PaymasterParams,
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+        TransactionReceipt, U256,
+    },
+};
+
+/// Gas per pubdata byte limit sent with L2 fee estimations.
+pub(crate) const DEFAULT_GAS_PER_PUBDATA_LIMIT: u64 = 50_000;
+
+/// Options shared by the commands that send transactions.
+#[derive(Parser, Clone)]
+pub struct TxOpts {
+    #[arg(
+        long,
+        required = false,
+        help = "Estimate the transaction fee and show it before sending"
+    )]
+    pub estimate: bool,
+    #[arg(long, short = 'y', required = false, help = "Skip the confirmation")]
+    pub yes: bool,
+    #[command(flatten)]
+    pub sign_opts: SignOpts,
+    #[command(flatten)]
+    pub safe_opts: SafeOpts,
+}
+
+impl TxOpts {
+    /// Asks whether to send the transaction, unless `--yes` is set.
+    pub fn confirm(&self, action: &str) -> eyre::Result<bool> {
+        if self.yes {
+            return Ok(true);
+        }
+        let confirmed = confirm(&format!("{action}?"))?;
+        if !confirmed {
+            println!("Aborted");
+        }
+        Ok(confirmed)
+    }
+}
+
+pub struct FeeEstimate {
+    pub gas_limit: U256,
+    /// Only set for L2 transactions.
+    pub gas_per_pubdata: Option<U256>,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+impl FeeEstimate {
+    pub fn total_cost(&self) -> U256 {
+        self.gas_limit.saturating_mul(self.max_fee_per_gas)
+    }
+
+    /// Prints the estimation, with the total cost in the token the fee is paid with.
+    pub fn display(&self, fee_token_decimals: i32, fee_token_symbol: &str) -> eyre::Result<()> {
+        println!("{}", "Fee estimation".bold());
+        println!("Gas limit: {}", self.gas_limit);
+        if let Some(gas_per_pubdata) = self.gas_per_pubdata {
+            println!("Gas per pubdata: {gas_per_pubdata}");
+        }
+        println!(
+            "Max fee per gas: {} gwei",
+            format_units(self.max_fee_per_gas, "gwei")?
+        );
+        println!(
+            "Max priority fee per gas: {} gwei",
+            format_units(self.max_priority_fee_per_gas, "gwei")?
+        );
+        println!(
+            "Max total cost: {} {fee_token_symbol}",
+            format_units(self.total_cost(), fee_token_decimals)?.bold()
+        );
+        Ok(())
+    }
+}
+
+/// Estimates the fee of an L2 transaction with `zks_estimateFee`.
+pub async fn estimate_l2_fee(
+    l2_provider: &Provider<Http>,
+    from: Address,
+    to: Address,
+    value: U256,
+    data: &[u8],
+    paymaster_params: Option<&PaymasterParams>,
+) -> eyre::Result<FeeEstimate> {
+    let mut eip712_meta = serde_json::json!({
+        "gasPerPubdata": U256::from(DEFAULT_GAS_PER_PUBDATA_LIMIT),
+    });
+    if let Some(paymaster_params) = paymaster_params {
+        eip712_meta["paymasterParams"] = serde_json::json!({
+            "paymaster": paymaster_params.paymaster,
+            "paymasterInput": paymaster_params.paymaster_input,
+        });
+    }
+    let estimate_request = serde_json::json!({
+        "from": from,
+        "to": to,
+        "value": value,
+        "data": Bytes::from(data.to_vec()),
+        "eip712Meta": eip712_meta,
+    });
+    let fee: serde_json::Value = l2_provider
+        .request("zks_estimateFee", [estimate_request])
+        .await?;
+    let fee_field = |field: &str| -> eyre::Result<U256> {
+        fee.get(field)
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .ok_or_else(|| eyre::eyre!("Fee estimation is missing {field}"))
+    };
+    Ok(FeeEstimate {
+        gas_limit: fee_field("gas_limit")?,
+        gas_per_pubdata: Some(fee_field("gas_per_pubdata_limit")?),
+        max_fee_per_gas: fee_field("max_fee_per_gas")?,
+        max_priority_fee_per_gas: fee_field("max_priority_fee_per_gas")?,
+    })
+}
+
+/// Completes an `eth_estimateGas` gas limit with the current L1 EIP-1559 fees.
+pub async fn estimate_l1_fee(
+    gas_limit: U256,
+    l1_provider: &Provider<Http>,
+) -> eyre::Result<FeeEstimate> {
+    let (max_fee_per_gas, max_priority_fee_per_gas) =
+        l1_provider.estimate_eip1559_fees(None).await?;
+    Ok(FeeEstimate {
+        gas_limit,
+        gas_per_pubdata: None,
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+    })
+}
+
+/// Sends an L1 transaction from `l1_signer` once confirmed, showing its fee estimation first
+/// if asked to. With `--sign-only` it is signed and written to the given file instead.
+/// If `safe` is set, the call is made by the Safe through `execTransaction` once enough
+/// owners signed it. Returns the receipt if the transaction was sent.
+pub(crate) async fn send_l1_tx<M: Middleware + 'static, S: Signer + 'static>(
+    l1_signer: &SignerMiddleware<M, S>,
+    to: Address,
+    data: Bytes,
+    safe: Option<Address>,
+    action: &str,
+    tx_opts: &TxOpts,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<Option<TransactionReceipt>> {
+    let (to, data) = match safe {
+        Some(safe) => {
+            let l1_provider = try_l1_provider_from_config(cfg)?;
+            let Some(exec_calldata) = safe_exec_transaction(
+                safe,
+                to,
+                data,
+                l1_signer.signer(),
+                &l1_provider,
+                &tx_opts.safe_opts,
+            )
+            .await?
+            else {
+                return Ok(None);
+            };
+            (safe, exec_calldata)
+        }
+        None => (to, data),
+    };
+    let tx: TypedTransaction = Eip1559TransactionRequest::new()
+        .from(l1_signer.address())
+        .to(to)
+        .data(data.clone())
+        .into();
+    if tx_opts.estimate {
+        let l1_provider = try_l1_provider_from_config(cfg)?;
+        let gas_limit = l1_provider.estimate_gas(&tx, None).await?;
+        estimate_l1_fee(gas_limit, &l1_provider)
+            .await?
+            .display(18_i32, "ETH")?;
+    }
+    if !tx_opts.confirm(action)? {
+        return Ok(None);
+    }
+    if let Some(signed_tx_path) = &tx_opts.sign_opts.sign_only {
+        let l1_provider = try_l1_provider_from_config(cfg)?;
+        sign_l1_transaction(
+            l1_signer.signer(),
+            to,
+            U256::zero(),
+            data,
+            &l1_provider,
+            &tx_opts.sign_opts,
+        )
+        .await?
+        .write(signed_tx_path)?;
+        return Ok(None);
+    }
+    let receipt = l1_signer
+        .send_transaction(tx, None)
+        .await?
+        .await?
+        .ok_or_else(|| eyre::eyre!("No transaction receipt for {to:?}"))?;
+    Ok(Some(receipt))
+}
@@ -0,0 +1,113 @@
+use crate::utils::contract::get_fn_selector;
+use std::{fmt::Display, str::FromStr};
+use zksync_ethers_rs::{
+    abi::{encode, Token},
+    types::{Address, Bytes, U256},
+};
+
+pub(crate) const STM_EXECUTE_UPGRADE: &str =
+    "executeUpgrade(uint256,((address,uint8,bool,bytes4[])[],address,bytes))";
+pub(crate) const UPGRADE_CHAIN_FROM_VERSION: &str =
+    "upgradeChainFromVersion(uint256,((address,uint8,bool,bytes4[])[],address,bytes))";
+
+/// `Diamond.Action`, what a facet cut does with its selectors.
+#[derive(Clone, Copy)]
+pub enum FacetAction {
+    Add,
+    Replace,
+    Remove,
+}
+
+impl FromStr for FacetAction {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(FacetAction::Add),
+            "replace" => Ok(FacetAction::Replace),
+            "remove" => Ok(FacetAction::Remove),
+            _ => eyre::bail!("Invalid facet action {s}, expected add, replace or remove"),
+        }
+    }
+}
+
+impl Display for FacetAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FacetAction::Add => write!(f, "add"),
+            FacetAction::Replace => write!(f, "replace"),
+            FacetAction::Remove => write!(f, "remove"),
+        }
+    }
+}
+
+/// `Diamond.FacetCut`, the removed selectors must have the zero address as facet.
+#[derive(Clone)]
+pub struct FacetCut {
+    pub facet: Address,
+    pub action: FacetAction,
+    pub is_freezable: bool,
+    pub selectors: Vec<[u8; 4]>,
+}
+
+impl FacetCut {
+    fn into_token(self) -> Token {
+        let action: u8 = match self.action {
+            FacetAction::Add => 0,
+            FacetAction::Replace => 1,
+            FacetAction::Remove => 2,
+        };
+        Token::Tuple(vec![
+            Token::Address(self.facet),
+            Token::Uint(action.into()),
+            Token::Bool(self.is_freezable),
+            Token::Array(
+                self.selectors
+                    .into_iter()
+                    .map(|selector| Token::FixedBytes(selector.to_vec()))
+                    .collect(),
+            ),
+        ])
+    }
+}
+
+/// `Diamond.DiamondCutData`, the facet cuts and the initialization delegate-called after them.
+pub struct DiamondCutData {
+    pub facet_cuts: Vec<FacetCut>,
+    pub init_address: Address,
+    pub init_calldata: Bytes,
+}
+
+impl DiamondCutData {
+    fn into_token(self) -> Token {
+        Token::Tuple(vec![
+            Token::Array(
+                self.facet_cuts
+                    .into_iter()
+                    .map(FacetCut::into_token)
+                    .collect(),
+            ),
+            Token::Address(self.init_address),
+            Token::Bytes(self.init_calldata.to_vec()),
+        ])
+    }
+
+    /// The hyperchain `upgradeChainFromVersion` calldata, called by the chain admin to upgrade
+    /// from the given protocol version.
+    pub fn upgrade_chain_from_version_calldata(self, from_protocol_version: U256) -> Bytes {
+        let mut calldata = get_fn_selector(UPGRADE_CHAIN_FROM_VERSION).to_vec();
+        calldata.extend(encode(&[
+            Token::Uint(from_protocol_version),
+            self.into_token(),
+        ]));
+        calldata.into()
+    }
+
+    /// The StateTransitionManager `executeUpgrade` calldata, called by its owner. Only the STM
+    /// can call the `executeUpgrade` of the hyperchain of `chain_id`.
+    pub fn stm_execute_upgrade_calldata(self, chain_id: U256) -> Bytes {
+        let mut calldata = get_fn_selector(STM_EXECUTE_UPGRADE).to_vec();
+        calldata.extend(encode(&[Token::Uint(chain_id), self.into_token()]));
+        calldata.into()
+    }
+}
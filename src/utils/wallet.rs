@@ -1,22 +1,40 @@
 use crate::config::ZKSyncConfig;
 use eyre::ContextCompat;
 use zksync_ethers_rs::{
+    abi::{decode, encode, ParamType, Token},
     middleware::SignerMiddleware,
     providers::{Http, Middleware, Provider},
-    signers::{LocalWallet, Signer},
+    signers::{coins_bip39::English, LocalWallet, MnemonicBuilder, Signer},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest,
+        TransactionRequest, H160, H256, U256,
+    },
+    utils::L2_ETH_TOKEN_ADDRESS,
     zk_wallet::ZKWallet,
+    ZKMiddleware,
 };
 
-use super::{try_l1_provider_from_config, try_l2_provider_from_config};
+use super::{
+    contract::get_fn_selector, contracts::bridgehub_address, signer::ConfigSigner,
+    try_l1_provider_from_config, try_l2_provider_from_config,
+};
+
+/// Base token address of the chains using ETH.
+pub(crate) const ETH_TOKEN_ADDRESS: Address = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x01,
+]);
+/// Gas per pubdata byte limit of the L1 to L2 transactions.
+const REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT: u64 = 800;
 
 type ZKWalletProvider = Provider<Http>;
 type SetupResult = (
-    ZKWallet<ZKWalletProvider, LocalWallet>,
+    ZKWallet<ZKWalletProvider, ConfigSigner>,
     ZKWalletProvider,
     ZKWalletProvider,
 );
 
-impl TryFrom<&ZKSyncConfig> for ZKWallet<ZKWalletProvider, LocalWallet> {
+impl TryFrom<&ZKSyncConfig> for ZKWallet<ZKWalletProvider, ConfigSigner> {
     type Error = eyre::Error;
 
     fn try_from(cfg: &ZKSyncConfig) -> eyre::Result<Self> {
@@ -33,12 +51,7 @@ impl TryFrom<&ZKSyncConfig> for ZKWallet<ZKWalletProvider, LocalWallet> {
             .l2_chain_id
             .context("L2 CHAIN_ID missing in config")?;
 
-        let wallet = cfg
-            .wallet
-            .clone()
-            .context("Wallet config missing")?
-            .private_key
-            .parse::<LocalWallet>()?;
+        let wallet = cfg.wallet_config()?.config_signer()?;
 
         let wallet = wallet.with_chain_id(l1_chain_id);
         let l1_signer = SignerMiddleware::new(l1_provider.clone(), wallet.clone());
@@ -76,3 +89,170 @@ pub fn get_wallet_l1_l2_providers(cfg: ZKSyncConfig) -> eyre::Result<SetupResult
 
     Ok((zk_wallet, l1_provider, l2_provider))
 }
+
+/// Derives the wallet at `m/44'/60'/0'/0/{index}` from a BIP-39 mnemonic.
+pub(crate) fn derive_wallet(mnemonic: &str, index: u32) -> eyre::Result<LocalWallet> {
+    MnemonicBuilder::<English>::default()
+        .phrase(mnemonic)
+        .index(index)?
+        .build()
+        .map_err(|e| eyre::eyre!("Failed to derive wallet {index} from mnemonic: {e}"))
+}
+
+/// Transfers ETH, or the given ERC20, on L1 and waits for the receipt.
+pub(crate) async fn l1_transfer(
+    zk_wallet: &ZKWallet<ZKWalletProvider, ConfigSigner>,
+    amount: U256,
+    token_address: Option<Address>,
+    to: Address,
+) -> eyre::Result<H256> {
+    let (tx_to, value, calldata) = transfer_call(amount, token_address, to);
+    let transfer_tx = TransactionRequest::new()
+        .from(zk_wallet.l1_address())
+        .to(tx_to)
+        .value(value)
+        .data(calldata);
+    let receipt = zk_wallet
+        .l1_signer()
+        .send_transaction(transfer_tx, None)
+        .await?
+        .await?
+        .context("No transaction receipt for L1 transfer")?;
+    if receipt.status.is_some_and(|status| status.is_zero()) {
+        eyre::bail!("L1 transfer {:?} reverted", receipt.transaction_hash);
+    }
+    Ok(receipt.transaction_hash)
+}
+
+/// The `(to, value, calldata)` of a transfer of the base token (ETH on L1), or of the given ERC20.
+pub(crate) fn transfer_call(
+    amount: U256,
+    token_address: Option<Address>,
+    to: Address,
+) -> (Address, U256, Vec<u8>) {
+    match token_address {
+        Some(token_address) => {
+            let mut calldata = get_fn_selector("transfer(address,uint256)").to_vec();
+            calldata.extend(encode(&[Token::Address(to), Token::Uint(amount)]));
+            (token_address, U256::zero(), calldata)
+        }
+        None => (to, amount, Vec::new()),
+    }
+}
+
+/// The `(to, value, calldata)` of a withdrawal of the base token to the L1 `receiver`, or of
+/// the given L2 ERC20 through the L2 shared bridge.
+pub(crate) async fn l2_withdraw_call(
+    amount: U256,
+    token_address: Option<Address>,
+    receiver: Address,
+    l2_provider: &ZKWalletProvider,
+) -> eyre::Result<(Address, U256, Vec<u8>)> {
+    match token_address {
+        Some(token_address) => {
+            let l2_shared_bridge = l2_provider
+                .get_bridge_contracts()
+                .await?
+                .l2_shared_default_bridge
+                .context("L2 Shared Bridge not set")?;
+            let mut calldata = get_fn_selector("withdraw(address,address,uint256)").to_vec();
+            calldata.extend(encode(&[
+                Token::Address(receiver),
+                Token::Address(token_address),
+                Token::Uint(amount),
+            ]));
+            Ok((l2_shared_bridge, U256::zero(), calldata))
+        }
+        None => {
+            let mut calldata = get_fn_selector("withdraw(address)").to_vec();
+            calldata.extend(encode(&[Token::Address(receiver)]));
+            Ok((L2_ETH_TOKEN_ADDRESS, amount, calldata))
+        }
+    }
+}
+
+/// Whether `l1_deposit_call` can build the deposit of the given token, only ETH deposits on
+/// the chains using ETH as base token are.
+pub(crate) async fn is_l1_deposit_call_supported(
+    token_address: Option<Address>,
+    l2_provider: &ZKWalletProvider,
+) -> eyre::Result<bool> {
+    Ok(token_address.is_none()
+        && l2_provider.get_base_token_l1_address().await? == ETH_TOKEN_ADDRESS)
+}
+
+/// The `(to, value, calldata)` of a deposit of `amount` ETH to `to` on L2 through the
+/// Bridgehub `requestL2TransactionDirect`. The value pays the L2 base cost at `max_fee_per_gas`,
+/// which must be at least the gas price of the L1 transaction. ERC20 deposits and the chains
+/// using another base token are not supported.
+pub(crate) async fn l1_deposit_call(
+    amount: U256,
+    token_address: Option<Address>,
+    from: Address,
+    to: Address,
+    max_fee_per_gas: U256,
+    cfg: &ZKSyncConfig,
+) -> eyre::Result<(Address, U256, Vec<u8>)> {
+    let l1_provider = try_l1_provider_from_config(cfg)?;
+    let l2_provider = try_l2_provider_from_config(cfg)?;
+    if !is_l1_deposit_call_supported(token_address, &l2_provider).await? {
+        eyre::bail!("Only ETH deposits on chains using ETH as base token are supported");
+    }
+    let bridgehub = bridgehub_address(cfg).await?;
+    let l2_chain_id = l2_provider.get_chainid().await?;
+    let l2_gas_limit: U256 = l2_provider
+        .request(
+            "zks_estimateGasL1ToL2",
+            [serde_json::json!({
+                "from": from,
+                "to": to,
+                "value": amount,
+                "data": Bytes::new(),
+                "eip712Meta": {
+                    "gasPerPubdata": U256::from(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT),
+                },
+            })],
+        )
+        .await?;
+
+    let mut base_cost_calldata =
+        get_fn_selector("l2TransactionBaseCost(uint256,uint256,uint256,uint256)").to_vec();
+    base_cost_calldata.extend(encode(&[
+        Token::Uint(l2_chain_id),
+        Token::Uint(max_fee_per_gas),
+        Token::Uint(l2_gas_limit),
+        Token::Uint(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT.into()),
+    ]));
+    let base_cost_call: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(bridgehub)
+        .data(base_cost_calldata)
+        .into();
+    let base_cost = decode(
+        &[ParamType::Uint(256)],
+        &l1_provider.call(&base_cost_call, None).await?,
+    )?
+    .into_iter()
+    .next()
+    .and_then(Token::into_uint)
+    .context("Bridgehub returned no L2 transaction base cost")?;
+    let mint_value = base_cost
+        .checked_add(amount)
+        .context("Deposit value overflow")?;
+
+    let mut calldata = get_fn_selector(
+        "requestL2TransactionDirect((uint256,uint256,address,uint256,bytes,uint256,uint256,bytes[],address))",
+    )
+    .to_vec();
+    calldata.extend(encode(&[Token::Tuple(vec![
+        Token::Uint(l2_chain_id),
+        Token::Uint(mint_value),
+        Token::Address(to),
+        Token::Uint(amount),
+        Token::Bytes(Vec::new()),
+        Token::Uint(l2_gas_limit),
+        Token::Uint(REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_LIMIT.into()),
+        Token::Array(Vec::new()),
+        Token::Address(from),
+    ])]));
+    Ok((bridgehub, mint_value, calldata))
+}
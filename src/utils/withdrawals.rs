@@ -0,0 +1,251 @@
+use crate::utils::config::zks_dir_path;
+use crate::utils::contract::get_fn_selector;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, path::PathBuf};
+use zksync_ethers_rs::{
+    abi::{decode, encode, ParamType, Token},
+    core::utils::keccak256,
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, H160,
+        H256, U256, U64,
+    },
+    ZKMiddleware,
+};
+
+/// Address of the L1Messenger system contract, emitting the L2 to L1 messages.
+const L1_MESSENGER_ADDRESS: Address = H160([
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x80, 0x08,
+]);
+
+/// A withdrawal sent from this CLI, kept until it is finalized on L1 or found failed on L2.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct PendingWithdrawal {
+    pub l2_chain_id: u64,
+    pub address: Address,
+    pub l2_tx_hash: H256,
+    pub token_address: Option<Address>,
+    pub amount: U256,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// The L2 transaction is not included in a block yet.
+    Pending,
+    Failed,
+    /// Included in an L2 block, its batch is not committed yet.
+    Included,
+    Committed,
+    Proven,
+    /// Its batch was executed but the withdrawal is not finalized on L1.
+    Finalizable,
+    Finalized,
+}
+
+impl Display for WithdrawalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self {
+            WithdrawalStatus::Pending => "Pending",
+            WithdrawalStatus::Failed => "Failed",
+            WithdrawalStatus::Included => "Included on L2",
+            WithdrawalStatus::Committed => "Batch committed",
+            WithdrawalStatus::Proven => "Batch proven",
+            WithdrawalStatus::Finalizable => "Finalizable",
+            WithdrawalStatus::Finalized => "Finalized",
+        };
+        write!(f, "{status}")
+    }
+}
+
+pub fn withdrawals_file_path() -> eyre::Result<PathBuf> {
+    Ok(zks_dir_path("")?.join("withdrawals.json"))
+}
+
+pub fn pending_withdrawals() -> eyre::Result<Vec<PendingWithdrawal>> {
+    let withdrawals_file_path = withdrawals_file_path()?;
+    if !withdrawals_file_path.exists() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&std::fs::read_to_string(withdrawals_file_path)?).map_err(Into::into)
+}
+
+/// The pending withdrawals of `address` in the given L2 chain.
+pub fn pending_withdrawals_of(
+    address: Address,
+    l2_chain_id: u64,
+) -> eyre::Result<Vec<PendingWithdrawal>> {
+    Ok(pending_withdrawals()?
+        .into_iter()
+        .filter(|w| w.address == address && w.l2_chain_id == l2_chain_id)
+        .collect())
+}
+
+pub fn save_pending_withdrawal(withdrawal: PendingWithdrawal) -> eyre::Result<()> {
+    let mut withdrawals = pending_withdrawals()?;
+    withdrawals.push(withdrawal);
+    write_pending_withdrawals(&withdrawals)
+}
+
+/// Drops the given withdrawal from the store, once it is finalized or failed.
+pub fn remove_pending_withdrawal(l2_tx_hash: H256) -> eyre::Result<()> {
+    let mut withdrawals = pending_withdrawals()?;
+    withdrawals.retain(|w| w.l2_tx_hash != l2_tx_hash);
+    write_pending_withdrawals(&withdrawals)
+}
+
+fn write_pending_withdrawals(withdrawals: &[PendingWithdrawal]) -> eyre::Result<()> {
+    std::fs::write(
+        withdrawals_file_path()?,
+        serde_json::to_string_pretty(withdrawals)?,
+    )?;
+    Ok(())
+}
+
+pub async fn withdrawal_status(
+    l2_tx_hash: H256,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<WithdrawalStatus> {
+    let Some(receipt) = l2_provider.get_transaction_receipt(l2_tx_hash).await? else {
+        return Ok(WithdrawalStatus::Pending);
+    };
+    if receipt.status.is_some_and(|status| status.is_zero()) {
+        return Ok(WithdrawalStatus::Failed);
+    }
+    let Some(l1_batch_number) = receipt
+        .other
+        .get_deserialized::<U64>("l1BatchNumber")
+        .transpose()?
+    else {
+        return Ok(WithdrawalStatus::Included);
+    };
+    let batch_details = l2_provider
+        .get_l1_batch_details(l1_batch_number.as_u32())
+        .await?;
+    if batch_details.base.execute_tx_hash.is_none() {
+        if batch_details.base.prove_tx_hash.is_some() {
+            return Ok(WithdrawalStatus::Proven);
+        }
+        if batch_details.base.commit_tx_hash.is_some() {
+            return Ok(WithdrawalStatus::Committed);
+        }
+        return Ok(WithdrawalStatus::Included);
+    }
+    if is_withdrawal_finalized(l2_tx_hash, l1_batch_number, l1_provider, l2_provider).await? {
+        Ok(WithdrawalStatus::Finalized)
+    } else {
+        Ok(WithdrawalStatus::Finalizable)
+    }
+}
+
+/// Calls `isWithdrawalFinalized` on the L1 shared bridge.
+async fn is_withdrawal_finalized(
+    l2_tx_hash: H256,
+    l1_batch_number: U64,
+    l1_provider: &Provider<Http>,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<bool> {
+    let l1_shared_bridge = l2_provider
+        .get_bridge_contracts()
+        .await?
+        .l1_shared_default_bridge
+        .ok_or_else(|| eyre::eyre!("L1 Shared Bridge not set"))?;
+    let log_proof: Option<serde_json::Value> = l2_provider
+        .request("zks_getL2ToL1LogProof", [l2_tx_hash])
+        .await?;
+    let l2_message_index = log_proof
+        .as_ref()
+        .and_then(|log_proof| log_proof.get("id"))
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| eyre::eyre!("No L2 to L1 log proof for withdrawal {l2_tx_hash:?}"))?;
+    let l2_chain_id = l2_provider.get_chainid().await?;
+
+    let mut calldata = get_fn_selector("isWithdrawalFinalized(uint256,uint256,uint256)").to_vec();
+    calldata.extend(encode(&[
+        Token::Uint(l2_chain_id),
+        Token::Uint(l1_batch_number.as_u64().into()),
+        Token::Uint(l2_message_index.into()),
+    ]));
+    let call: TypedTransaction = Eip1559TransactionRequest::new()
+        .to(l1_shared_bridge)
+        .data(Bytes::from(calldata))
+        .into();
+    let result = l1_provider.call(&call, None).await?;
+    Ok(result.iter().any(|byte| *byte != 0))
+}
+
+/// The `(to, calldata)` of the `finalizeWithdrawal` L1 shared bridge call of an executed
+/// withdrawal, built from its L2 to L1 message and log proof.
+pub(crate) async fn finalize_withdrawal_call(
+    l2_tx_hash: H256,
+    l2_provider: &Provider<Http>,
+) -> eyre::Result<(Address, Vec<u8>)> {
+    let receipt = l2_provider
+        .get_transaction_receipt(l2_tx_hash)
+        .await?
+        .ok_or_else(|| eyre::eyre!("No L2 receipt for withdrawal {l2_tx_hash:?}"))?;
+    let receipt_field = |field: &str| -> eyre::Result<U64> {
+        receipt
+            .other
+            .get_deserialized::<U64>(field)
+            .transpose()?
+            .ok_or_else(|| eyre::eyre!("Withdrawal {l2_tx_hash:?} receipt is missing {field}"))
+    };
+    let l1_batch_number = receipt_field("l1BatchNumber")?;
+    let l1_batch_tx_index = receipt_field("l1BatchTxIndex")?;
+    let message_sent_topic = H256(keccak256("L1MessageSent(address,bytes32,bytes)"));
+    let message_log = receipt
+        .logs
+        .iter()
+        .find(|log| {
+            log.address == L1_MESSENGER_ADDRESS && log.topics.first() == Some(&message_sent_topic)
+        })
+        .ok_or_else(|| eyre::eyre!("No L2 to L1 message in withdrawal {l2_tx_hash:?}"))?;
+    let message = decode(&[ParamType::Bytes], &message_log.data)?
+        .into_iter()
+        .next()
+        .and_then(Token::into_bytes)
+        .ok_or_else(|| eyre::eyre!("Invalid L2 to L1 message in withdrawal {l2_tx_hash:?}"))?;
+
+    let log_proof: Option<serde_json::Value> = l2_provider
+        .request("zks_getL2ToL1LogProof", [l2_tx_hash])
+        .await?;
+    let log_proof = log_proof
+        .ok_or_else(|| eyre::eyre!("No L2 to L1 log proof for withdrawal {l2_tx_hash:?}"))?;
+    let l2_message_index = log_proof
+        .get("id")
+        .and_then(serde_json::Value::as_u64)
+        .ok_or_else(|| eyre::eyre!("L2 to L1 log proof of {l2_tx_hash:?} is missing its id"))?;
+    let merkle_proof: Vec<H256> = log_proof
+        .get("proof")
+        .cloned()
+        .map(serde_json::from_value)
+        .transpose()?
+        .ok_or_else(|| eyre::eyre!("L2 to L1 log proof of {l2_tx_hash:?} is missing its proof"))?;
+
+    let l1_shared_bridge = l2_provider
+        .get_bridge_contracts()
+        .await?
+        .l1_shared_default_bridge
+        .ok_or_else(|| eyre::eyre!("L1 Shared Bridge not set"))?;
+    let l2_chain_id = l2_provider.get_chainid().await?;
+
+    let mut calldata =
+        get_fn_selector("finalizeWithdrawal(uint256,uint256,uint256,uint16,bytes,bytes32[])")
+            .to_vec();
+    calldata.extend(encode(&[
+        Token::Uint(l2_chain_id),
+        Token::Uint(l1_batch_number.as_u64().into()),
+        Token::Uint(l2_message_index.into()),
+        Token::Uint(l1_batch_tx_index.as_u64().into()),
+        Token::Bytes(message),
+        Token::Array(
+            merkle_proof
+                .into_iter()
+                .map(|hash| Token::FixedBytes(hash.as_bytes().to_vec()))
+                .collect(),
+        ),
+    ]));
+    Ok((l1_shared_bridge, calldata))
+}